@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use libuv_sys2::{self as ffi, uv_async_t};
 
@@ -6,13 +7,48 @@ use crate::Handle;
 
 type Callback = Box<dyn FnMut() -> Result<(), Box<dyn Error>> + 'static>;
 
+/// The data stored in the libuv handle.
+struct Data {
+    callback: Callback,
+
+    /// Set when the last clone of the [`AsyncHandle`] is dropped, signaling
+    /// the async callback that it should close the handle.
+    ///
+    /// The lock is held while waking up the loop, so once the callback sees
+    /// this set no other thread can still be using the handle.
+    closing: Mutex<bool>,
+}
+
+/// A handle to a callback registered on the Neovim event loop, which can be
+/// cloned and sent to other threads. The libuv handle is closed and the
+/// callback is freed when the last clone is dropped.
 #[derive(Clone)]
 pub struct AsyncHandle {
-    handle: Handle<uv_async_t, Callback>,
+    inner: Arc<Inner>,
 }
 
-unsafe impl Send for AsyncHandle {}
-unsafe impl Sync for AsyncHandle {}
+struct Inner {
+    handle: Handle<uv_async_t, Data>,
+}
+
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // The last clone can be dropped on any thread, but `uv_close` can
+        // only be called on the main thread. Instead we wake up the loop and
+        // let the async callback close the handle.
+        //
+        // The data is only freed after the callback has seen the flag, which
+        // can't happen before we release the lock.
+        let closing = unsafe { &(*self.handle.get_data()).closing };
+        let mut closing =
+            closing.lock().unwrap_or_else(PoisonError::into_inner);
+        *closing = true;
+        unsafe { ffi::uv_async_send(self.handle.as_mut_ptr()) };
+    }
+}
 
 impl AsyncHandle {
     /// Registers a new callback on the Neovim event loop, returning an
@@ -36,9 +72,11 @@ impl AsyncHandle {
             callback().map_err(|err| Box::new(err) as Box<dyn Error>)
         });
 
-        unsafe { handle.set_data(callback) };
+        unsafe {
+            handle.set_data(Data { callback, closing: Mutex::new(false) })
+        };
 
-        Ok(Self { handle })
+        Ok(Self { inner: Arc::new(Inner { handle }) })
     }
 
     /// Wakes up the Neovim event loop and executes the callback associated to
@@ -50,12 +88,14 @@ impl AsyncHandle {
     /// example: if [`AsyncHandle::send`] is called 5 times in a row before the
     /// callback is called, the callback will only be called once. If
     /// [`AsyncHandle::send`] is called again after the callback was called, it
-    /// will be called again.
+    /// will be called again. This also means that a call made right before
+    /// the last clone of the handle is dropped may not execute the callback.
     ///
     /// [libuv]: https://libuv.org/
     pub fn send(&self) -> Result<(), crate::Error> {
-        let retv =
-            unsafe { ffi::uv_async_send(self.handle.as_ptr() as *mut _) };
+        let retv = unsafe {
+            ffi::uv_async_send(self.inner.handle.as_ptr() as *mut _)
+        };
 
         if retv < 0 {
             // TODO
@@ -67,15 +107,28 @@ impl AsyncHandle {
 }
 
 extern "C" fn async_cb(ptr: *mut uv_async_t) {
-    let handle: Handle<_, Callback> = unsafe { Handle::from_raw(ptr) };
+    let mut handle: Handle<_, Data> = unsafe { Handle::from_raw(ptr) };
+
+    let data = unsafe { handle.get_data() };
 
-    let callback = unsafe { handle.get_data() };
+    if data.is_null() {
+        return;
+    }
 
-    if !callback.is_null() {
-        let callback = unsafe { &mut *callback };
+    // Only take references to the fields, since `closing` can be accessed
+    // from other threads at the same time.
+    let closing = unsafe { &(*data).closing };
 
-        if let Err(_err) = callback() {
-            // TODO: what now?
-        }
+    if *closing.lock().unwrap_or_else(PoisonError::into_inner) {
+        // The data is freed in the close callback, so it can't be accessed
+        // after this.
+        unsafe { handle.close() };
+        return;
+    }
+
+    let callback = unsafe { &mut (*data).callback };
+
+    if let Err(_err) = callback() {
+        // TODO: what now?
     }
 }
@@ -69,4 +69,29 @@ impl<T, D> Handle<T, D> {
             data as *mut c_void,
         )
     }
+
+    /// Closes the handle. Its data and the memory it was allocated in are
+    /// freed once libuv is done with it, so the handle (and any copy of its
+    /// pointer) must not be used after calling this.
+    ///
+    /// NOTE: like every libuv function except `uv_async_send` this is not
+    /// thread-safe, and it must only be called on the main thread.
+    pub(crate) unsafe fn close(&mut self) {
+        ffi::uv_close(
+            self.as_mut_ptr() as *mut uv_handle_t,
+            Some(close_cb::<T, D> as _),
+        )
+    }
+}
+
+extern "C" fn close_cb<T, D: 'static>(ptr: *mut uv_handle_t) {
+    let handle: Handle<T, D> = unsafe { Handle::from_raw(ptr as *mut T) };
+
+    let data = unsafe { handle.get_data() };
+
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(data) });
+    }
+
+    unsafe { alloc::dealloc(ptr as *mut u8, Layout::new::<T>()) };
 }
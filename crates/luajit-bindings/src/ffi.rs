@@ -67,6 +67,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_getmetatable
     pub fn lua_getmetatable(L: *mut lua_State, index: c_int) -> c_int;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_gettable
+    pub fn lua_gettable(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_gettop
     pub fn lua_gettop(L: *mut lua_State) -> c_int;
 
@@ -123,6 +126,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_rawseti
     pub fn lua_rawseti(L: *mut lua_State, index: c_int, n: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_setmetatable
+    pub fn lua_setmetatable(L: *mut lua_State, index: c_int) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_settop
     pub fn lua_settop(L: *mut lua_State, index: c_int);
 
@@ -142,6 +148,9 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_tonumber
     pub fn lua_tonumber(L: *mut lua_State, index: c_int) -> lua_Number;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_topointer
+    pub fn lua_topointer(L: *mut lua_State, index: c_int) -> *const c_void;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_touserdata
     pub fn lua_touserdata(L: *mut lua_State, index: c_int) -> *mut c_void;
 
@@ -156,6 +165,14 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#luaL_error
     pub fn luaL_error(L: *mut lua_State, fmt: *const c_char, ...) -> !;
 
+    // https://www.lua.org/manual/5.1/manual.html#luaL_loadbuffer
+    pub fn luaL_loadbuffer(
+        L: *mut lua_State,
+        buff: *const c_char,
+        sz: usize,
+        name: *const c_char,
+    ) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#luaL_ref
     pub fn luaL_ref(L: *mut lua_State, t: c_int) -> c_int;
 
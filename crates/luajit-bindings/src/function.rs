@@ -55,33 +55,43 @@ where
         crate::with_state(move |lstate| {
             ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, lua_ref);
             let nargs = args.push(lstate)?;
+            pcall(lstate, nargs)
+        })
+    }
+}
 
-            match ffi::lua_pcall(lstate, nargs, -1, 0 /* <- errorfunc */) {
-                ffi::LUA_OK => R::pop(lstate),
-
-                err_code => {
-                    let msg = CStr::from_ptr(ffi::lua_tostring(lstate, -1))
-                        .to_string_lossy()
-                        .to_string();
+/// Calls the function below the `nargs` arguments at the top of the stack in
+/// protected mode, popping its return value as an `R` or turning the error it
+/// raised into a [`crate::Error`].
+pub unsafe fn pcall<R>(
+    lstate: *mut lua_State,
+    nargs: c_int,
+) -> Result<R, crate::Error>
+where
+    R: Poppable,
+{
+    match ffi::lua_pcall(lstate, nargs, -1, 0 /* <- errorfunc */) {
+        ffi::LUA_OK => R::pop(lstate),
 
-                    ffi::lua_pop(lstate, 1);
+        err_code => {
+            let msg = CStr::from_ptr(ffi::lua_tostring(lstate, -1))
+                .to_string_lossy()
+                .to_string();
 
-                    match err_code {
-                        ffi::LUA_ERRRUN => {
-                            Err(crate::Error::RuntimeError(msg))
-                        },
+            ffi::lua_pop(lstate, 1);
 
-                        ffi::LUA_ERRMEM => Err(crate::Error::MemoryError(msg)),
+            match err_code {
+                ffi::LUA_ERRRUN => Err(crate::Error::RuntimeError(msg)),
 
-                        ffi::LUA_ERRERR => {
-                            unreachable!("errorfunc is 0, this never happens!")
-                        },
+                ffi::LUA_ERRMEM => Err(crate::Error::MemoryError(msg)),
 
-                        _ => unreachable!(),
-                    }
+                ffi::LUA_ERRERR => {
+                    unreachable!("errorfunc is 0, this never happens!")
                 },
+
+                _ => unreachable!(),
             }
-        })
+        },
     }
 }
 
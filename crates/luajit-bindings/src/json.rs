@@ -0,0 +1,164 @@
+//! `Pushable` and `Poppable` implementations for [`serde_json::Value`].
+//!
+//! JSON `null`s are mapped to a `NULL` light userdata (i.e. Neovim's
+//! `vim.NIL`) so that they can be stored in tables without creating holes.
+//! When popping, both `nil` and `vim.NIL` become `null`s.
+//!
+//! Lua 5.1 doesn't distinguish between integers and floats, so numbers with no
+//! fractional component are always popped as integers.
+
+use std::ffi::c_int;
+
+use serde_json::{Map, Number, Value};
+
+use crate::ffi::*;
+use crate::{utils, Error, Poppable, Pushable};
+
+impl Pushable for Value {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int, Error> {
+        match self {
+            Value::Null => lua_pushlightuserdata(lstate, std::ptr::null_mut()),
+
+            Value::Bool(b) => {
+                b.push(lstate)?;
+            },
+
+            Value::Number(n) => {
+                if let Some(n) = n.as_i64() {
+                    n.push(lstate)?;
+                } else {
+                    n.as_f64().unwrap_or_default().push(lstate)?;
+                }
+            },
+
+            Value::String(s) => {
+                lua_pushlstring(lstate, s.as_ptr() as *const _, s.len())
+            },
+
+            Value::Array(values) => {
+                lua_createtable(lstate, values.len() as _, 0);
+
+                for (i, value) in values.into_iter().enumerate() {
+                    value.push(lstate)?;
+                    lua_rawseti(lstate, -2, (i + 1) as _);
+                }
+            },
+
+            Value::Object(map) => {
+                lua_createtable(lstate, 0, map.len() as _);
+
+                // Give empty objects a metatable so that they're not confused
+                // with empty arrays when popped.
+                if map.is_empty() {
+                    lua_createtable(lstate, 0, 0);
+                    lua_setmetatable(lstate, -2);
+                }
+
+                for (key, value) in map {
+                    lua_pushlstring(
+                        lstate,
+                        key.as_ptr() as *const _,
+                        key.len(),
+                    );
+                    value.push(lstate)?;
+                    lua_rawset(lstate, -3);
+                }
+            },
+        }
+
+        Ok(1)
+    }
+}
+
+impl Poppable for Value {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self, Error> {
+        if lua_gettop(lstate) == 0 {
+            return Err(Error::PopEmptyStack);
+        }
+
+        match lua_type(lstate, -1) {
+            LUA_TNIL => <()>::pop(lstate).map(|()| Value::Null),
+
+            LUA_TLIGHTUSERDATA if lua_touserdata(lstate, -1).is_null() => {
+                lua_pop(lstate, 1);
+                Ok(Value::Null)
+            },
+
+            LUA_TBOOLEAN => bool::pop(lstate).map(Value::Bool),
+
+            LUA_TNUMBER => {
+                let n = lua_Number::pop(lstate)?;
+
+                if n.fract() == 0.0 && n.abs() < i64::MAX as lua_Number {
+                    Ok(Value::Number((n as i64).into()))
+                } else {
+                    Number::from_f64(n).map(Value::Number).ok_or_else(|| {
+                        Error::pop_error(
+                            "serde_json::Value",
+                            format!("{n} is not a valid JSON number"),
+                        )
+                    })
+                }
+            },
+
+            LUA_TSTRING => {
+                <String as Poppable>::pop(lstate).map(Value::String)
+            },
+
+            LUA_TTABLE => {
+                if utils::is_table_array(lstate, -1) {
+                    let values = (1..=lua_objlen(lstate, -1))
+                        .map(|i| {
+                            lua_rawgeti(lstate, -1, i as _);
+                            <Value as Poppable>::pop(lstate)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    lua_pop(lstate, 1);
+
+                    Ok(Value::Array(values))
+                } else {
+                    let mut map = Map::new();
+
+                    lua_pushnil(lstate);
+
+                    while lua_next(lstate, -2) != 0 {
+                        let value = <Value as Poppable>::pop(lstate)?;
+
+                        if lua_type(lstate, -1) != LUA_TSTRING {
+                            let ty = utils::debug_type(lstate, -1);
+                            lua_pop(lstate, 2);
+                            return Err(Error::pop_error(
+                                "serde_json::Value",
+                                format!(
+                                    "object keys must be strings, found a \
+                                     {ty}"
+                                ),
+                            ));
+                        }
+
+                        // NOTE: `String::pop` will pop the key, so we push
+                        // another copy of it for the next iteration.
+                        lua_pushvalue(lstate, -1);
+                        let key = <String as Poppable>::pop(lstate)?;
+
+                        map.insert(key, value);
+                    }
+
+                    lua_pop(lstate, 1);
+
+                    Ok(Value::Object(map))
+                }
+            },
+
+            _ => {
+                let ty = utils::debug_type(lstate, -1);
+                lua_pop(lstate, 1);
+                Err(Error::pop_error(
+                    "serde_json::Value",
+                    format!("unexpected value of type {ty}"),
+                ))
+            },
+        }
+    }
+}
@@ -2,6 +2,8 @@
 mod error;
 pub mod ffi;
 pub mod function;
+#[cfg(feature = "serde_json")]
+mod json;
 pub mod macros;
 mod poppable;
 mod pushable;
@@ -13,4 +15,4 @@ pub use error::Error;
 pub use macros::__print;
 pub use poppable::Poppable;
 pub use pushable::Pushable;
-pub use state::{init, with_state};
+pub use state::{init, is_main_thread, with_state};
@@ -23,6 +23,9 @@ pub(crate) use count;
 /// Binding to the global Lua `print` function. It uses the same syntax as
 /// Rust's `format!` macro and redirects its output to the Neovim message area.
 ///
+/// Neovim `String`s and `Object`s can be printed directly: strings are lossily
+/// decoded as UTF-8, while objects can be pretty-printed with `{:#}`.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -30,6 +33,7 @@ pub(crate) use count;
 ///
 /// nvim::print!("Goodbye {}..", String::from("Earth"));
 /// nvim::print!("Hello {planet}!", planet = "Mars");
+/// nvim::print!("{:#}", nvim::Object::from(nvim::Array::from((1, 2, 3))));
 /// ```
 #[macro_export]
 macro_rules! print {
@@ -111,6 +111,40 @@ impl Pushable for f32 {
     }
 }
 
+impl Pushable for String {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        self.as_str().push(lstate)
+    }
+}
+
+impl Pushable for &str {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        ffi::lua_pushlstring(lstate, self.as_ptr() as *const _, self.len());
+        Ok(1)
+    }
+}
+
+impl<T: Pushable> Pushable for Option<T> {
+    unsafe fn push(
+        self,
+        lstate: *mut lua_State,
+    ) -> Result<c_int, crate::Error> {
+        match self {
+            Some(value) => value.push(lstate),
+            None => {
+                ffi::lua_pushnil(lstate);
+                Ok(1)
+            },
+        }
+    }
+}
+
 impl<T: Pushable> Pushable for Vec<T> {
     unsafe fn push(
         self,
@@ -25,3 +25,10 @@ where
 {
     LUA.with(move |lstate| fun(*lstate.get().unwrap_unchecked()))
 }
+
+/// Returns whether the Lua state was initialized on the current thread. Since
+/// Neovim loads modules on its main thread, this is `false` on any thread
+/// spawned by the module.
+pub fn is_main_thread() -> bool {
+    LUA.try_with(|lstate| lstate.get().is_some()).unwrap_or(false)
+}
@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_types::LuaFunction;
+
+use crate::Buffer;
+
+/// A callback registered by [`Buffer::attach_guarded`], which is released by
+/// its [`AttachGuard`] when the latter is dropped.
+pub(crate) type GuardedCallback = Rc<RefCell<Option<Rc<LuaFunction>>>>;
+
+/// A guard returned by [`Buffer::attach_guarded`] which detaches the
+/// registered callbacks when dropped.
+///
+/// `nvim_buf_detach` can only be called over RPC, so Neovim is handed small
+/// wrappers around the callbacks instead. Dropping the guard releases the
+/// references to the callbacks right away, while the wrappers return `true`
+/// the next time they're invoked, which asks Neovim to detach and free them
+/// as described in `:h api-lua-detach`.
+#[derive(Debug)]
+#[must_use = "the callbacks are detached as soon as the guard is dropped"]
+pub struct AttachGuard {
+    buffer: Buffer,
+    callbacks: Vec<GuardedCallback>,
+}
+
+impl AttachGuard {
+    pub(crate) fn new(
+        buffer: Buffer,
+        callbacks: Vec<GuardedCallback>,
+    ) -> Self {
+        Self { buffer, callbacks }
+    }
+
+    /// Returns the buffer the callbacks are attached to.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Detaches the callbacks. Same as dropping the guard.
+    pub fn detach(self) {}
+}
+
+impl Drop for AttachGuard {
+    fn drop(&mut self) {
+        for callback in &self.callbacks {
+            callback.borrow_mut().take();
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use nvim_types::{self as nvim, Array, FromObject, Integer, Object};
+use nvim_types::{self as nvim, Array, FromObject, Function, Integer, Object};
 
 use super::ffi::autocmd::*;
 use super::opts::*;
@@ -117,3 +117,25 @@ pub fn get_autocmds(
         infos.into_iter().map(|obj| AutocmdInfos::from_obj(obj).unwrap())
     })
 }
+
+/// Registers a callback invoked every time the current mode changes, using a
+/// `ModeChanged` autocommand. Returns the id of the autocommand, which can be
+/// passed to [`del_autocmd`] to stop listening.
+///
+/// The old and new modes are parsed from the `old:new` match string of the
+/// event. Changes from or to modes without a corresponding [`Mode`] variant,
+/// like the hit-enter prompt, are skipped.
+pub fn on_mode_change<F>(mut callback: F) -> Result<u32>
+where
+    F: FnMut(ModeChangeArgs) -> Result<()> + 'static,
+{
+    let callback = Function::from_fn_mut(move |args: AutocmdCallbackArgs| {
+        if let Some(args) = ModeChangeArgs::from_match(args.r#match) {
+            callback(args)?;
+        }
+        Ok::<_, crate::Error>(false)
+    });
+
+    let opts = CreateAutocmdOpts::builder().callback(callback).build();
+    self::create_autocmd(["ModeChanged"], &opts)
+}
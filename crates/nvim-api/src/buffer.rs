@@ -1,5 +1,8 @@
+use std::cell::RefCell;
 use std::fmt;
+use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use luajit_bindings::{self as lua, Poppable, Pushable};
 use nvim_types::{
@@ -11,7 +14,9 @@ use nvim_types::{
     FromObjectResult,
     Function,
     Integer,
+    LuaFunction,
     Object,
+    ObjectKind,
     ToObject,
     ToObjectResult,
 };
@@ -20,15 +25,34 @@ use serde::{Deserialize, Serialize};
 use super::ffi::buffer::*;
 use super::opts::*;
 use super::LUA_INTERNAL_CALL;
+use crate::attach_guard::GuardedCallback;
 use crate::iterator::SuperIterator;
+use crate::line_diff;
 use crate::trait_utils::StringOrFunction;
-use crate::types::{CommandArgs, CommandInfos, KeymapInfos, Mode};
-use crate::{Error, Result};
+use crate::types::{
+    AutocmdCallbackArgs,
+    BufType,
+    CommandArgs,
+    CommandInfos,
+    Diagnostic,
+    Index,
+    KeymapInfos,
+    Mode,
+    TextEdit,
+};
+use crate::{AttachGuard, Error, Result};
 
 /// A newtype struct wrapping a Neovim buffer. All the `nvim_buf_*` functions
 /// taking a buffer handle as their first argument are implemented as methods
 /// on this object.
+///
+/// A `Buffer` (de)serializes to its integer handle, so it can be stored in
+/// plugin state, e.g. with [`set_var`](crate::set_var). Note that handles
+/// are only meaningful within the Neovim session that created them: a
+/// restored buffer may not be valid anymore, or may refer to a different
+/// buffer after a restart.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Buffer(pub(crate) BufHandle);
 
 impl fmt::Debug for Buffer {
@@ -86,6 +110,15 @@ impl Poppable for Buffer {
     }
 }
 
+impl Pushable for Buffer {
+    unsafe fn push(
+        self,
+        lstate: *mut luajit_bindings::ffi::lua_State,
+    ) -> std::result::Result<std::ffi::c_int, lua::Error> {
+        self.0.push(lstate)
+    }
+}
+
 impl Buffer {
     /// Shorthand for
     /// [`nvim_oxi::api::get_current_buf`](crate::api::get_current_buf).
@@ -94,6 +127,50 @@ impl Buffer {
         crate::get_current_buf()
     }
 
+    /// Applies a list of [`TextEdit`]s to the buffer, with all positions
+    /// referring to the buffer's contents *before* any edit is applied.
+    ///
+    /// The edits are applied from the bottom of the buffer to the top, so
+    /// that no edit shifts the positions of the ones still to be applied.
+    /// Insertions at the same position end up in the order they're given in.
+    /// Returns an error without touching the buffer if any two edits overlap.
+    pub fn apply_edits(&mut self, edits: &[TextEdit]) -> Result<()> {
+        let mut edits = edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|edit| (edit.start, edit.end));
+
+        for edit in &edits {
+            if edit.start > edit.end {
+                return Err(Error::custom(format!(
+                    "edit start {:?} is after its end {:?}",
+                    edit.start, edit.end
+                )));
+            }
+        }
+
+        for pair in edits.windows(2) {
+            if pair[0].end > pair[1].start {
+                return Err(Error::custom(format!(
+                    "overlapping edits at {:?} and {:?}",
+                    pair[0].start, pair[1].start
+                )));
+            }
+        }
+
+        for edit in edits.into_iter().rev() {
+            let (start_row, start_col) = edit.start;
+            let (end_row, end_col) = edit.end;
+            self.set_text(
+                start_row,
+                start_col,
+                end_row,
+                end_col,
+                edit.new_text.split('\n'),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Binding to [`nvim_buf_attach`](https://neovim.io/doc/user/api.html#nvim_buf_attach()).
     ///
     /// Used to register a set of callbacks on specific buffer events.
@@ -119,6 +196,62 @@ impl Buffer {
         })
     }
 
+    /// Like [`Buffer::attach`], but the callbacks stay attached only until
+    /// the returned [`AttachGuard`] is dropped, one of them returns `true` or
+    /// the buffer is unloaded.
+    ///
+    /// Note that `let _ = buf.attach_guarded(..)` drops the guard right away,
+    /// detaching the callbacks before they're ever called. Bind the guard to
+    /// a named variable instead, or use [`Buffer::attach`] to keep the
+    /// callbacks attached for the lifetime of the buffer.
+    pub fn attach_guarded(
+        &self,
+        send_buffer: bool,
+        opts: &BufAttachOpts,
+    ) -> Result<AttachGuard> {
+        let mut callbacks = Vec::new();
+
+        let opts = Dictionary::from(opts)
+            .into_iter()
+            .map(|(key, value)| {
+                if value.kind() != ObjectKind::LuaRef {
+                    return Ok((key, value));
+                }
+                let value = match key.as_bytes() {
+                    b"on_bytes" => {
+                        detachable::<OnBytesArgs>(value, &mut callbacks)?
+                    },
+                    b"on_changedtick" => {
+                        detachable::<OnChangedtickArgs>(value, &mut callbacks)?
+                    },
+                    b"on_lines" => {
+                        detachable::<OnLinesArgs>(value, &mut callbacks)?
+                    },
+                    b"on_reload" => {
+                        detachable::<OnReloadArgs>(value, &mut callbacks)?
+                    },
+                    _ => value,
+                };
+                Ok((key, value))
+            })
+            .collect::<Result<Dictionary>>()?;
+
+        let mut err = nvim::Error::new();
+        let has_attached = unsafe {
+            nvim_buf_attach(
+                LUA_INTERNAL_CALL,
+                self.0,
+                send_buffer,
+                opts.non_owning(),
+                &mut err,
+            )
+        };
+        err.into_err_or_flatten(|| match has_attached {
+            true => Ok(AttachGuard::new(self.clone(), callbacks)),
+            _ => Err(Error::custom("Attaching to buffer failed")),
+        })
+    }
+
     /// Binding to [`nvim_buf_call`](https://neovim.io/doc/user/api.html#nvim_buf_call()).
     ///
     /// Calls a function with this buffer as the temporary current buffer.
@@ -186,16 +319,14 @@ impl Buffer {
 
     /// Binding to [`nvim_buf_del_mark`](https://neovim.io/doc/user/api.html#nvim_buf_del_mark()).
     ///
-    /// Deletes a named mark in the buffer.
-    pub fn del_mark(&mut self, name: char) -> Result<()> {
+    /// Deletes a named mark in the buffer. Returns `true` if the mark was
+    /// deleted, `false` if it wasn't set.
+    pub fn del_mark(&mut self, name: char) -> Result<bool> {
         let mut err = nvim::Error::new();
         let name = nvim::String::from(name);
         let was_deleted =
             unsafe { nvim_buf_del_mark(self.0, name.non_owning(), &mut err) };
-        err.into_err_or_flatten(|| match was_deleted {
-            true => Ok(()),
-            _ => Err(Error::custom("Couldn't delete mark")),
-        })
+        err.into_err_or_else(|| was_deleted)
     }
 
     /// Binding to [`nvim_buf_del_user_command`](https://neovim.io/doc/user/api.html#nvim_buf_del_user_command()).
@@ -233,6 +364,27 @@ impl Buffer {
         err.into_err_or_else(|| ())
     }
 
+    /// Gets the diagnostics reported through `vim.diagnostic` that start on a
+    /// line in the 0-indexed `line_range`, across all diagnostic namespaces.
+    ///
+    /// Diagnostic extmarks only store positions, so the diagnostics are read
+    /// with `vim.diagnostic.get()` instead, which is where their messages,
+    /// severities and sources live.
+    pub fn diagnostics(
+        &self,
+        line_range: impl RangeBounds<usize>,
+    ) -> Result<Vec<Diagnostic>> {
+        let diagnostics = crate::exec_lua_object(
+            "return vim.diagnostic.get(...)",
+            Array::from_iter([self.clone()]),
+        )?;
+
+        Ok(Vec::<Diagnostic>::from_obj(diagnostics)?
+            .into_iter()
+            .filter(|diagnostic| line_range.contains(&diagnostic.line))
+            .collect())
+    }
+
     /// Binding to [`nvim_buf_get_changedtick`](https://neovim.io/doc/user/api.html#nvim_buf_get_changedtick()).
     pub fn get_changedtick(&self) -> Result<u32> {
         let mut err = nvim::Error::new();
@@ -280,19 +432,25 @@ impl Buffer {
     /// end-exclusive. Out of bounds indices are clamped to the nearest valid
     /// value, unless `strict_indexing` is set, in which case passing an
     /// invalid index will cause an error.
+    ///
+    /// Both `start` and `end` can be either `usize`s or [`Index`]es, the
+    /// latter allowing to count lines from the end of the buffer. Passing a
+    /// `start` that comes after `end` returns an error if both are counted
+    /// from the same side.
     pub fn get_lines(
         &self,
-        start: usize,
-        end: usize,
+        start: impl Into<Index>,
+        end: impl Into<Index>,
         strict_indexing: bool,
     ) -> Result<impl SuperIterator<nvim::String>> {
+        let (start, end) = index_range(start.into(), end.into())?;
         let mut err = nvim::Error::new();
         let lines = unsafe {
             nvim_buf_get_lines(
                 LUA_INTERNAL_CALL,
                 self.0,
-                start.try_into()?,
-                end.try_into()?,
+                start,
+                end,
                 strict_indexing,
                 &mut err,
             )
@@ -328,6 +486,44 @@ impl Buffer {
         err.into_err_or_else(|| name.into())
     }
 
+    /// Gets the lines in the 0-indexed `range`, each one paired with its
+    /// 1-indexed line number. Out of bounds indices are clamped to the
+    /// nearest valid value.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Yields the 6th to 10th lines.
+    /// for (lnum, line) in buf.get_numbered_lines(5..10)? {
+    ///     nvim_oxi::print!("{lnum}: {line}");
+    /// }
+    /// ```
+    pub fn get_numbered_lines(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<impl SuperIterator<(usize, nvim::String)>> {
+        // Neovim clamps out of bounds indices, but they still have to fit in
+        // an `Integer`.
+        let max = Integer::MAX as usize;
+
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n.min(max),
+            Bound::Excluded(&n) => n.saturating_add(1).min(max),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => {
+                Index::FromStart(n.saturating_add(1).min(max))
+            },
+            Bound::Excluded(&n) => Index::FromStart(n.min(max)),
+            Bound::Unbounded => Index::FromEnd(0),
+        };
+
+        let lines = self.get_lines(start, end, false)?;
+        Ok(lines.enumerate().map(move |(i, line)| (start + i + 1, line)))
+    }
+
     /// Binding to [`nvim_buf_get_offset`](https://neovim.io/doc/user/api.html#nvim_buf_get_offset()).
     ///
     /// Returns the 0-indexed byte offset of a line.
@@ -359,7 +555,8 @@ impl Buffer {
     /// that it allows retrieving only portions of a line.
     ///
     /// Indexing is zero-based, with both row and column indices being
-    /// end-exclusive.
+    /// end-exclusive. Returns an error if `(start_row, start_col)` comes
+    /// after `(end_row, end_col)`.
     pub fn get_text(
         &self,
         start_row: usize,
@@ -368,6 +565,7 @@ impl Buffer {
         end_col: usize,
         opts: &GetTextOpts,
     ) -> Result<impl SuperIterator<nvim::String>> {
+        check_range((start_row, start_col), (end_row, end_col))?;
         let mut err = nvim::Error::new();
         let opts = Dictionary::from(opts);
         let lines = unsafe {
@@ -424,6 +622,63 @@ impl Buffer {
         err.into_err_or_else(|| count.try_into().expect("always positive"))
     }
 
+    /// Registers a callback invoked once when the buffer is wiped out, using a
+    /// buffer-local `BufWipeout` autocommand. Returns the id of the
+    /// autocommand.
+    ///
+    /// Useful to drop any state associated with the buffer: unlike the
+    /// [`on_detach`](BufAttachOptsBuilder::on_detach) callback of
+    /// [`attach`](Buffer::attach), this runs even if the callbacks were
+    /// detached earlier or were never attached at all.
+    pub fn on_wipe<F>(&self, callback: F) -> Result<u32>
+    where
+        F: FnOnce() -> Result<()> + 'static,
+    {
+        let callback =
+            Function::from_fn_once(move |_: AutocmdCallbackArgs| {
+                callback().map(|()| false)
+            });
+
+        let opts = CreateAutocmdOpts::builder()
+            .buffer(self.clone())
+            .once(true)
+            .callback(callback)
+            .build();
+
+        crate::create_autocmd(["BufWipeout"], &opts)
+    }
+
+    /// Like [`Buffer::get_lines`] with `strict_indexing` set, but writes the
+    /// lines into `out` instead of allocating a new collection. The previous
+    /// contents of `out` are replaced, reusing both the vector's and the
+    /// strings' allocations. Lines that aren't valid UTF-8 are converted
+    /// lossily.
+    pub fn read_lines_into(
+        &self,
+        start: impl Into<Index>,
+        end: impl Into<Index>,
+        out: &mut Vec<String>,
+    ) -> Result<()> {
+        let mut len = 0;
+
+        for line in self.get_lines(start, end, true)? {
+            let line = line.to_string_lossy();
+
+            match out.get_mut(len) {
+                Some(existing) => {
+                    existing.clear();
+                    existing.push_str(&line);
+                },
+                None => out.push(line.into_owned()),
+            }
+
+            len += 1;
+        }
+
+        out.truncate(len);
+        Ok(())
+    }
+
     /// Binding to [`nvim_buf_set_keymap`](https://neovim.io/doc/user/api.html#nvim_buf_set_keymap()).
     ///
     /// Sets a buffer-local mapping for the given mode. To set a global mapping
@@ -458,10 +713,17 @@ impl Buffer {
     ///
     /// Sets (replaces) a line-range in the buffer. Indexing is zero-based,
     /// end-exclusive.
+    ///
+    /// Like for [`Buffer::get_lines`], `start` and `end` can be either
+    /// `usize`s or [`Index`]es. For example, passing `2` and
+    /// `Index::FromEnd(0)` replaces everything from the third line to the end
+    /// of the buffer, while passing [`Buffer::line_count`] as both `start` and
+    /// `end` appends the replacement after the last line. Like for
+    /// [`Buffer::get_lines`], an inverted range returns an error.
     pub fn set_lines<Line, Lines>(
         &mut self,
-        start: usize,
-        end: usize,
+        start: impl Into<Index>,
+        end: impl Into<Index>,
         strict_indexing: bool,
         replacement: Lines,
     ) -> Result<()>
@@ -469,14 +731,15 @@ impl Buffer {
         Lines: IntoIterator<Item = Line>,
         Line: Into<nvim::String>,
     {
+        let (start, end) = index_range(start.into(), end.into())?;
         let rpl = replacement.into_iter().map(Into::into).collect::<Array>();
         let mut err = nvim::Error::new();
         unsafe {
             nvim_buf_set_lines(
                 LUA_INTERNAL_CALL,
                 self.0,
-                start.try_into()?,
-                end.try_into()?,
+                start,
+                end,
                 strict_indexing,
                 rpl.non_owning(),
                 &mut err,
@@ -485,6 +748,49 @@ impl Buffer {
         err.into_err_or_else(|| ())
     }
 
+    /// Replaces the contents of the buffer with `new_lines`, only touching
+    /// the lines that actually changed.
+    ///
+    /// The old and new contents are diffed line by line, and each run of
+    /// inserted, deleted or changed lines is applied with its own call to
+    /// [`Buffer::set_lines`], from the bottom of the buffer up. Since
+    /// unchanged lines are never rewritten, the extmarks placed on them are
+    /// preserved.
+    pub fn set_lines_diff(&mut self, new_lines: &[&str]) -> Result<()> {
+        let old_lines =
+            self.get_lines(0, Index::FromEnd(0), true)?.collect::<Vec<_>>();
+
+        // The lines shared at the start and at the end are skipped before
+        // diffing, since that's cheaper.
+        let prefix = old_lines
+            .iter()
+            .zip(new_lines)
+            .take_while(|(old, new)| *old == *new)
+            .count();
+
+        let suffix = old_lines[prefix..]
+            .iter()
+            .rev()
+            .zip(new_lines[prefix..].iter().rev())
+            .take_while(|(old, new)| *old == *new)
+            .count();
+
+        let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+        let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+        // Going bottom-up keeps the indices of the hunks above valid.
+        for hunk in line_diff::diff(old_mid, new_mid).into_iter().rev() {
+            self.set_lines(
+                prefix + hunk.old.start,
+                prefix + hunk.old.end,
+                true,
+                new_mid[hunk.new].iter().copied(),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Binding to [`nvim_buf_set_mark`](https://neovim.io/doc/user/api.html#nvim_buf_set_mark()).
     ///
     /// Sets a named mark in the buffer. Marks are (1,0)-indexed, and passing 0
@@ -601,3 +907,103 @@ impl Buffer {
         err.into_err_or_else(|| ())
     }
 }
+
+impl Buffer {
+    /// Shorthand for setting the `buftype` option.
+    #[inline(always)]
+    pub fn set_buftype(&mut self, buftype: BufType) -> Result<()> {
+        self.set_option("buftype", buftype)
+    }
+
+    /// Shorthand for setting the `filetype` option.
+    #[inline(always)]
+    pub fn set_filetype(&mut self, filetype: &str) -> Result<()> {
+        self.set_option("filetype", filetype)
+    }
+
+    /// Shorthand for setting the `modifiable` option.
+    #[inline(always)]
+    pub fn set_modifiable(&mut self, modifiable: bool) -> Result<()> {
+        self.set_option("modifiable", modifiable)
+    }
+
+    /// Sets the prompt of a prompt buffer (i.e. one whose `buftype` is
+    /// [`BufType::Prompt`]) by calling the `prompt_setprompt()` Vimscript
+    /// function.
+    pub fn set_prompt(&mut self, prompt: &str) -> Result<()> {
+        crate::call_function::<_, Integer>(
+            "prompt_setprompt",
+            (self.clone(), prompt),
+        )
+        .map(|_| ())
+    }
+
+    /// Sets the function called when a line is submitted in a prompt buffer
+    /// by calling the `prompt_setcallback()` Vimscript function. The callback
+    /// receives the entered text, without the prompt.
+    pub fn set_prompt_callback<F>(&mut self, callback: F) -> Result<()>
+    where
+        F: FnMut(String) -> Result<()> + 'static,
+    {
+        let callback = Function::from_fn_mut(callback);
+        crate::call_function::<_, Integer>(
+            "prompt_setcallback",
+            (self.clone(), callback),
+        )
+        .map(|_| ())
+    }
+}
+
+/// Wraps a callback passed to [`Buffer::attach_guarded`] in a function which
+/// forwards its arguments to the callback until the [`AttachGuard`] releases
+/// it, and then asks Neovim to detach it by returning `true`.
+fn detachable<A>(
+    callback: Object,
+    callbacks: &mut Vec<GuardedCallback>,
+) -> Result<Object>
+where
+    A: Poppable + Pushable + 'static,
+{
+    let callback = LuaFunction::from_obj(callback)?;
+    let callback = Rc::new(RefCell::new(Some(Rc::new(callback))));
+    callbacks.push(Rc::clone(&callback));
+
+    let wrapper = Function::from_fn(move |args: A| {
+        // Not holding the borrow while calling the callback lets it drop the
+        // guard.
+        let callback = callback.borrow().clone();
+        match callback {
+            Some(callback) => callback.call::<_, ShouldDetach>(args),
+            None => Ok(true),
+        }
+    });
+
+    Ok(wrapper.into())
+}
+
+/// Returns an error if the `start` of a range comes after its `end`.
+pub(crate) fn check_range<T>(start: T, end: T) -> Result<()>
+where
+    T: PartialOrd + fmt::Debug,
+{
+    if start > end {
+        return Err(Error::custom(format!("start {start:?} > end {end:?}")));
+    }
+    Ok(())
+}
+
+/// Converts a line range into Neovim's indices, checking that it's not
+/// inverted. Ranges mixing [`Index::FromStart`] and [`Index::FromEnd`] can't
+/// be checked without knowing the length of the buffer, so they're left to
+/// Neovim.
+pub(crate) fn index_range(
+    start: Index,
+    end: Index,
+) -> Result<(Integer, Integer)> {
+    let start = Integer::try_from(start)?;
+    let end = Integer::try_from(end)?;
+    if (start < 0) == (end < 0) {
+        check_range(start, end)?;
+    }
+    Ok((start, end))
+}
@@ -0,0 +1,393 @@
+use std::path::PathBuf;
+
+use crate::opts::SetExtmarkOpts;
+use crate::types::{ExtmarkId, Index, Namespace};
+use crate::{Buffer, Result};
+
+/// The buffer operations most commonly used by plugin logic, abstracted over
+/// so that the logic can be tested without a running Neovim instance.
+///
+/// [`Buffer`] implements this trait by forwarding to its inherent methods,
+/// while [`MockBuffer`] (behind the `testing` feature) provides an in-memory
+/// implementation.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{types::Index, BufferOps, Result};
+///
+/// fn trim_trailing_whitespace<B: BufferOps>(buf: &mut B) -> Result<()> {
+///     let lines = buf.get_lines(0.into(), Index::FromEnd(0), true)?;
+///     let trimmed = lines.iter().map(|line| line.trim_end().to_owned());
+///     buf.set_lines(0.into(), Index::FromEnd(0), true, trimmed.collect())
+/// }
+/// ```
+pub trait BufferOps {
+    /// See [`Buffer::line_count`].
+    fn line_count(&self) -> Result<usize>;
+
+    /// See [`Buffer::get_lines`]. Lines are lossily decoded as UTF-8.
+    fn get_lines(
+        &self,
+        start: Index,
+        end: Index,
+        strict_indexing: bool,
+    ) -> Result<Vec<String>>;
+
+    /// See [`Buffer::set_lines`].
+    fn set_lines(
+        &mut self,
+        start: Index,
+        end: Index,
+        strict_indexing: bool,
+        replacement: Vec<String>,
+    ) -> Result<()>;
+
+    /// See [`Buffer::get_name`].
+    fn get_name(&self) -> Result<PathBuf>;
+
+    /// See [`Buffer::set_extmark`].
+    fn set_extmark(
+        &mut self,
+        ns_id: Namespace,
+        line: usize,
+        col: usize,
+        opts: &SetExtmarkOpts,
+    ) -> Result<ExtmarkId>;
+
+    /// See [`Buffer::del_extmark`].
+    fn del_extmark(
+        &mut self,
+        ns_id: Namespace,
+        extmark_id: ExtmarkId,
+    ) -> Result<()>;
+
+    /// See [`Buffer::clear_namespace`].
+    fn clear_namespace(
+        &mut self,
+        ns_id: Namespace,
+        line_start: usize,
+        line_end: usize,
+    ) -> Result<()>;
+}
+
+impl BufferOps for Buffer {
+    #[inline]
+    fn line_count(&self) -> Result<usize> {
+        Buffer::line_count(self)
+    }
+
+    #[inline]
+    fn get_lines(
+        &self,
+        start: Index,
+        end: Index,
+        strict_indexing: bool,
+    ) -> Result<Vec<String>> {
+        Ok(Buffer::get_lines(self, start, end, strict_indexing)?
+            .map(|line| line.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    #[inline]
+    fn set_lines(
+        &mut self,
+        start: Index,
+        end: Index,
+        strict_indexing: bool,
+        replacement: Vec<String>,
+    ) -> Result<()> {
+        Buffer::set_lines(self, start, end, strict_indexing, replacement)
+    }
+
+    #[inline]
+    fn get_name(&self) -> Result<PathBuf> {
+        Buffer::get_name(self)
+    }
+
+    #[inline]
+    fn set_extmark(
+        &mut self,
+        ns_id: Namespace,
+        line: usize,
+        col: usize,
+        opts: &SetExtmarkOpts,
+    ) -> Result<ExtmarkId> {
+        Buffer::set_extmark(self, ns_id, line, col, opts)
+    }
+
+    #[inline]
+    fn del_extmark(
+        &mut self,
+        ns_id: Namespace,
+        extmark_id: ExtmarkId,
+    ) -> Result<()> {
+        Buffer::del_extmark(self, ns_id, extmark_id)
+    }
+
+    #[inline]
+    fn clear_namespace(
+        &mut self,
+        ns_id: Namespace,
+        line_start: usize,
+        line_end: usize,
+    ) -> Result<()> {
+        Buffer::clear_namespace(self, ns_id, line_start, line_end)
+    }
+}
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use mock::MockBuffer;
+
+#[cfg(feature = "testing")]
+mod mock {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use super::BufferOps;
+    use crate::buffer::{check_range, index_range};
+    use crate::opts::SetExtmarkOpts;
+    use crate::types::{ExtmarkId, Index, Namespace};
+    use crate::{Error, Result};
+
+    /// An in-memory implementation of [`BufferOps`] for testing plugin logic
+    /// without a running Neovim instance.
+    ///
+    /// Lines are indexed like in Neovim, i.e. out of bounds indices are
+    /// clamped unless `strict_indexing` is set. Extmarks only record their
+    /// position, the options passed to
+    /// [`set_extmark`](BufferOps::set_extmark) are ignored.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct MockBuffer {
+        /// The name of the buffer.
+        pub name: PathBuf,
+
+        /// The lines of the buffer.
+        pub lines: Vec<String>,
+
+        /// The `(line, col)` positions of the extmarks in the buffer.
+        pub extmarks: BTreeMap<(Namespace, ExtmarkId), (usize, usize)>,
+
+        next_extmark_id: u32,
+    }
+
+    impl MockBuffer {
+        /// Creates a new mock buffer containing `lines`.
+        pub fn new<Line, Lines>(lines: Lines) -> Self
+        where
+            Lines: IntoIterator<Item = Line>,
+            Line: Into<String>,
+        {
+            let mut lines =
+                lines.into_iter().map(Into::into).collect::<Vec<_>>();
+
+            // Like in Neovim, a buffer always has at least one line.
+            if lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            Self { lines, ..Default::default() }
+        }
+
+        /// Converts an [`Index`] into an offset in `self.lines`.
+        fn offset(
+            &self,
+            index: Index,
+            strict_indexing: bool,
+        ) -> Result<usize> {
+            let len = self.lines.len();
+
+            let offset = match index {
+                Index::FromStart(n) => Some(n),
+                Index::FromEnd(n) => len.checked_sub(n),
+            };
+
+            match offset {
+                Some(offset) if offset <= len => Ok(offset),
+                _ if strict_indexing => {
+                    Err(Error::custom("Index out of bounds"))
+                },
+                Some(_) => Ok(len),
+                None => Ok(0),
+            }
+        }
+    }
+
+    impl BufferOps for MockBuffer {
+        fn line_count(&self) -> Result<usize> {
+            Ok(self.lines.len())
+        }
+
+        fn get_lines(
+            &self,
+            start: Index,
+            end: Index,
+            strict_indexing: bool,
+        ) -> Result<Vec<String>> {
+            index_range(start, end)?;
+            let start = self.offset(start, strict_indexing)?;
+            let end = self.offset(end, strict_indexing)?;
+
+            if start > end {
+                return Err(Error::custom(
+                    "Argument \"start\" is higher than \"end\"",
+                ));
+            }
+
+            Ok(self.lines[start..end].to_vec())
+        }
+
+        fn set_lines(
+            &mut self,
+            start: Index,
+            end: Index,
+            strict_indexing: bool,
+            replacement: Vec<String>,
+        ) -> Result<()> {
+            index_range(start, end)?;
+            let start = self.offset(start, strict_indexing)?;
+            let end = self.offset(end, strict_indexing)?;
+
+            if start > end {
+                return Err(Error::custom(
+                    "Argument \"start\" is higher than \"end\"",
+                ));
+            }
+
+            self.lines.splice(start..end, replacement);
+
+            if self.lines.is_empty() {
+                self.lines.push(String::new());
+            }
+
+            Ok(())
+        }
+
+        fn get_name(&self) -> Result<PathBuf> {
+            Ok(self.name.clone())
+        }
+
+        fn set_extmark(
+            &mut self,
+            ns_id: Namespace,
+            line: usize,
+            col: usize,
+            _opts: &SetExtmarkOpts,
+        ) -> Result<ExtmarkId> {
+            if line >= self.lines.len() {
+                return Err(Error::custom("Invalid 'line': out of range"));
+            }
+
+            if col > self.lines[line].len() {
+                return Err(Error::custom("Invalid 'col': out of range"));
+            }
+
+            self.next_extmark_id += 1;
+            let id = ExtmarkId(self.next_extmark_id);
+            self.extmarks.insert((ns_id, id), (line, col));
+            Ok(id)
+        }
+
+        fn del_extmark(
+            &mut self,
+            ns_id: Namespace,
+            extmark_id: ExtmarkId,
+        ) -> Result<()> {
+            match self.extmarks.remove(&(ns_id, extmark_id)) {
+                Some(_) => Ok(()),
+                None => Err(Error::custom(format!(
+                    "No extmark with id {extmark_id} was found"
+                ))),
+            }
+        }
+
+        fn clear_namespace(
+            &mut self,
+            ns_id: Namespace,
+            line_start: usize,
+            line_end: usize,
+        ) -> Result<()> {
+            check_range(line_start, line_end)?;
+            self.extmarks.retain(|&(ns, _), &mut (line, _)| {
+                ns != ns_id || line < line_start || line >= line_end
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Some plugin logic that only depends on `BufferOps`.
+        fn trim_trailing_whitespace<B: BufferOps>(buf: &mut B) -> Result<()> {
+            let lines = buf.get_lines(0.into(), Index::FromEnd(0), true)?;
+            let trimmed = lines.iter().map(|line| line.trim_end().to_owned());
+            buf.set_lines(0.into(), Index::FromEnd(0), true, trimmed.collect())
+        }
+
+        #[test]
+        fn mock_trim_trailing_whitespace() {
+            let mut buf = MockBuffer::new(["foo  ", "bar", "\tbaz\t"]);
+            trim_trailing_whitespace(&mut buf).unwrap();
+            assert_eq!(vec!["foo", "bar", "\tbaz"], buf.lines);
+        }
+
+        #[test]
+        fn mock_indexing() {
+            let mut buf = MockBuffer::new(["a", "b", "c"]);
+
+            let last =
+                buf.get_lines(Index::FromEnd(1), Index::FromEnd(0), true);
+            assert_eq!(Ok(vec!["c".to_owned()]), last);
+
+            assert!(buf.get_lines(0.into(), 4.into(), true).is_err());
+            assert_eq!(
+                3,
+                buf.get_lines(0.into(), 4.into(), false).unwrap().len()
+            );
+
+            buf.set_lines(3.into(), 3.into(), true, vec!["d".into()]).unwrap();
+            assert_eq!(Ok(4), buf.line_count());
+
+            buf.set_lines(0.into(), Index::FromEnd(0), true, vec![]).unwrap();
+            assert_eq!(vec![String::new()], buf.lines);
+        }
+
+        #[test]
+        fn mock_inverted_ranges() {
+            let mut buf = MockBuffer::new(["a", "b", "c"]);
+
+            let err = buf.get_lines(2.into(), 1.into(), false).unwrap_err();
+            assert_eq!("start 2 > end 1", err.to_string());
+
+            let err = buf
+                .set_lines(Index::FromEnd(1), Index::FromEnd(2), false, vec![])
+                .unwrap_err();
+            assert_eq!("start -2 > end -3", err.to_string());
+
+            let ns = Namespace(1);
+            assert!(buf.clear_namespace(ns, 2, 1).is_err());
+            assert_eq!(vec!["a", "b", "c"], buf.lines);
+        }
+
+        #[test]
+        fn mock_extmarks() {
+            let mut buf = MockBuffer::new(["foo", "bar"]);
+            let ns = Namespace(1);
+            let opts = SetExtmarkOpts::default();
+
+            let first = buf.set_extmark(ns, 0, 3, &opts).unwrap();
+            let second = buf.set_extmark(ns, 1, 0, &opts).unwrap();
+            assert_ne!(first, second);
+            assert!(buf.set_extmark(ns, 2, 0, &opts).is_err());
+
+            buf.clear_namespace(ns, 1, usize::MAX).unwrap();
+            assert_eq!(1, buf.extmarks.len());
+
+            assert_eq!(Ok(()), buf.del_extmark(ns, first));
+            assert!(buf.del_extmark(ns, first).is_err());
+        }
+    }
+}
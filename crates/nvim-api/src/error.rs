@@ -24,12 +24,18 @@ pub enum Error {
     #[error(transparent)]
     IntError(#[from] std::num::TryFromIntError),
 
+    #[error(transparent)]
+    LuaError(#[from] luajit_bindings::Error),
+
     #[error("{0}")]
     SerializeError(String),
 
     #[error("{0}")]
     DeserializeError(String),
 
+    #[error("Invalid window id: {}", .0.0)]
+    InvalidWindow(crate::Window),
+
     #[error("{0}")]
     Other(String),
 }
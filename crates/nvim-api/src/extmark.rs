@@ -4,6 +4,7 @@ use super::ffi::extmark::*;
 use super::opts::*;
 use super::types::*;
 use super::Buffer;
+use crate::buffer::check_range;
 use crate::iterator::SuperIterator;
 use crate::{Error, Result};
 
@@ -13,6 +14,12 @@ impl Buffer {
     /// Adds a highlight to the buffer. `line`, `col_start` and `col_end` are
     /// all 0-indexed. You can also pass `-1` to `col_end` to highlight to end
     /// of line.
+    ///
+    /// Returns the id of the namespace the highlight was added to, which can
+    /// later be passed to [`clear_namespace`](Buffer::clear_namespace) to
+    /// remove it. Passing `0` as `ns_id` allocates a new anonymous namespace,
+    /// while passing `-1` adds an ungrouped highlight, in which case `None`
+    /// is returned.
     pub fn add_highlight<I, L, S, E>(
         &mut self,
         ns_id: I,
@@ -20,7 +27,7 @@ impl Buffer {
         line: L,
         col_start: S,
         col_end: E,
-    ) -> Result<i64>
+    ) -> Result<Option<Namespace>>
     where
         I: Into<Integer>,
         L: Into<Integer>,
@@ -40,7 +47,9 @@ impl Buffer {
                 &mut err,
             )
         };
-        err.into_err_or_else(|| ns_id)
+        // Neovim returns the `ns_id` it was given, or the newly allocated
+        // one if it was `0`.
+        err.into_err_or_else(|| u32::try_from(ns_id).ok().map(Namespace))
     }
 
     /// Binding to [`nvim_buf_clear_namespace`](https://neovim.io/doc/user/api.html#nvim_buf_clear_namespace()).
@@ -48,21 +57,27 @@ impl Buffer {
     /// Clears namespaced objects like highlights, extmarks, or virtual text
     /// from a region.
     ///
-    /// Lines are 0-indexed. It's possible to clear the namespace in the entire
-    /// buffer by specifying `line_start = 0` and `line_end = -1`.
+    /// Lines are 0-indexed, and a `line_end` past the end of the buffer is
+    /// clamped to it. It's possible to clear the namespace in the entire
+    /// buffer by specifying `line_start = 0` and `line_end = usize::MAX`.
+    /// Passing a `line_start` greater than `line_end` returns an error.
     pub fn clear_namespace(
         &mut self,
-        ns_id: u32,
+        ns_id: impl Into<Namespace>,
         line_start: usize,
         line_end: usize,
     ) -> Result<()> {
+        check_range(line_start, line_end)?;
+        // Saturate the bounds instead of letting them wrap around when
+        // converted to an `Integer`.
+        let max = Integer::MAX as usize;
         let mut err = nvim::Error::new();
         unsafe {
             nvim_buf_clear_namespace(
                 self.0,
-                ns_id as Integer,
-                line_start as Integer,
-                line_end as Integer,
+                Integer::from(ns_id.into()),
+                line_start.min(max) as Integer,
+                line_end.min(max) as Integer,
                 &mut err,
             )
         };
@@ -72,13 +87,18 @@ impl Buffer {
     /// Binding to [`nvim_buf_del_extmark`](https://neovim.io/doc/user/api.html#nvim_buf_del_extmark()).
     ///
     /// Removes an extmark from the buffer.
-    pub fn del_extmark(&mut self, ns_id: u32, extmark_id: u32) -> Result<()> {
+    pub fn del_extmark(
+        &mut self,
+        ns_id: impl Into<Namespace>,
+        extmark_id: impl Into<ExtmarkId>,
+    ) -> Result<()> {
+        let extmark_id = extmark_id.into();
         let mut err = nvim::Error::new();
         let was_found = unsafe {
             nvim_buf_del_extmark(
                 self.0,
-                ns_id as Integer,
-                extmark_id as Integer,
+                Integer::from(ns_id.into()),
+                extmark_id.into(),
                 &mut err,
             )
         };
@@ -98,17 +118,18 @@ impl Buffer {
     /// option field was set to `true`.
     pub fn get_extmark_by_id(
         &self,
-        ns_id: u32,
-        extmark_id: u32,
+        ns_id: impl Into<Namespace>,
+        extmark_id: impl Into<ExtmarkId>,
         opts: &GetExtmarkByIdOpts,
     ) -> Result<(usize, usize, Option<ExtmarkInfos>)> {
+        let extmark_id = extmark_id.into();
         let opts = Dictionary::from(opts);
         let mut err = nvim::Error::new();
         let tuple = unsafe {
             nvim_buf_get_extmark_by_id(
                 self.0,
-                ns_id as Integer,
-                extmark_id as Integer,
+                Integer::from(ns_id.into()),
+                extmark_id.into(),
                 opts.non_owning(),
                 &mut err,
             )
@@ -136,20 +157,24 @@ impl Buffer {
     /// the `infos` are present only if the
     /// [`details`](crate::api::opts::GetExtmarksOptsBuilder::details) option
     /// field was set to `true`.
+    ///
+    /// If `start` comes after `end` the buffer is traversed in reverse, and
+    /// the extmarks are returned from the last one to the first one.
     pub fn get_extmarks(
         &self,
-        ns_id: u32,
+        ns_id: impl Into<Namespace>,
         start: ExtmarkPosition,
         end: ExtmarkPosition,
         opts: &GetExtmarksOpts,
-    ) -> Result<impl SuperIterator<(u32, usize, usize, Option<ExtmarkInfos>)>>
-    {
+    ) -> Result<
+        impl SuperIterator<(ExtmarkId, usize, usize, Option<ExtmarkInfos>)>,
+    > {
         let opts = Dictionary::from(opts);
         let mut err = nvim::Error::new();
         let extmarks = unsafe {
             nvim_buf_get_extmarks(
                 self.0,
-                ns_id as Integer,
+                Integer::from(ns_id.into()),
                 start.into(),
                 end.into(),
                 opts.non_owning(),
@@ -160,6 +185,7 @@ impl Buffer {
             extmarks.into_iter().map(|tuple| {
                 let mut iter = Array::from_obj(tuple).unwrap().into_iter();
                 let id = u32::from_obj(iter.next().expect("id is present"))
+                    .map(ExtmarkId)
                     .unwrap();
                 let row =
                     usize::from_obj(iter.next().expect("row is present"))
@@ -177,52 +203,85 @@ impl Buffer {
         })
     }
 
+    /// Like [`Buffer::get_extmarks`], but the extmarks are sorted by their
+    /// `(row, col)` position instead of being returned in traversal order,
+    /// which is reversed if `start` comes after `end`.
+    pub fn get_extmarks_sorted(
+        &self,
+        ns_id: impl Into<Namespace>,
+        start: ExtmarkPosition,
+        end: ExtmarkPosition,
+        opts: &GetExtmarksOpts,
+    ) -> Result<
+        impl SuperIterator<(ExtmarkId, usize, usize, Option<ExtmarkInfos>)>,
+    > {
+        let mut extmarks =
+            self.get_extmarks(ns_id, start, end, opts)?.collect::<Vec<_>>();
+        extmarks.sort_by_key(|&(_, row, col, _)| (row, col));
+        Ok(extmarks.into_iter())
+    }
+
     /// Binding to [`nvim_buf_set_extmark`](https://neovim.io/doc/user/api.html#nvim_buf_set_extmark()).
     ///
     /// Creates or updates an extmark. Both `line` and `col` are 0-indexed.
     /// Returns the id of the created/updated extmark.
+    ///
+    /// If the [`id`](crate::opts::SetExtmarkOptsBuilder::id) option is set
+    /// the extmark with that id is moved to the new position in place (or
+    /// created if there's none), and the same id is returned. Otherwise a
+    /// new extmark is always created.
     pub fn set_extmark(
         &mut self,
-        ns_id: u32,
+        ns_id: impl Into<Namespace>,
         line: usize,
         col: usize,
         opts: &SetExtmarkOpts,
-    ) -> Result<u32> {
+    ) -> Result<ExtmarkId> {
         let mut err = nvim::Error::new();
         let id = unsafe {
             nvim_buf_set_extmark(
                 self.0,
-                ns_id as Integer,
+                Integer::from(ns_id.into()),
                 line as Integer,
                 col as Integer,
                 &opts.0,
                 &mut err,
             )
         };
-        err.into_err_or_else(|| id.try_into().expect("always positive"))
+        err.into_err_or_else(|| {
+            ExtmarkId(id.try_into().expect("always positive"))
+        })
     }
 }
 
 /// Binding to [`nvim_create_namespace`](https://neovim.io/doc/user/api.html#nvim_create_namespace()).
 ///
 /// Creates a new namespace or gets the id of an existing one. If `name`
-/// matches an existing namespace the associated id is returned.
-pub fn create_namespace(name: &str) -> u32 {
+/// matches an existing namespace the associated id is returned, so calling
+/// this multiple times with the same name is idempotent. Use
+/// [`get_namespace_id`] to look up a namespace without creating it.
+pub fn create_namespace(name: &str) -> Namespace {
     let name = nvim::String::from(name);
-    unsafe { nvim_create_namespace(name.non_owning()) }
-        .try_into()
-        .expect("always positive")
+    let id = unsafe { nvim_create_namespace(name.non_owning()) };
+    Namespace(id.try_into().expect("always positive"))
+}
+
+/// Returns the id of the namespace called `name`, or `None` if it hasn't been
+/// created yet. Unlike [`create_namespace`] this never creates a new
+/// namespace.
+pub fn get_namespace_id(name: &str) -> Option<Namespace> {
+    get_namespaces().find_map(|(ns_name, id)| (ns_name == name).then_some(id))
 }
 
 /// Binding to [`nvim_get_namespaces`](https://neovim.io/doc/user/api.html#nvim_get_namespaces()).
 ///
 /// Returns an iterator over all the existing, non-anonymous namespace names
 /// and ids tuples `(name, id)`.
-pub fn get_namespaces() -> impl SuperIterator<(String, u32)> {
+pub fn get_namespaces() -> impl SuperIterator<(String, Namespace)> {
     unsafe { nvim_get_namespaces() }.into_iter().map(|(k, v)| {
         let k = k.try_into().expect("namespace name is valid UTF-8");
         let v = u32::from_obj(v).expect("namespace id is positive");
-        (k, v)
+        (k, Namespace(v))
     })
 }
 
@@ -230,14 +289,14 @@ pub fn get_namespaces() -> impl SuperIterator<(String, u32)> {
 ///
 /// Sets or changes a decoration provider for a namespace.
 pub fn set_decoration_provider(
-    ns_id: u32,
+    ns_id: impl Into<Namespace>,
     opts: &DecorationProviderOpts,
 ) -> Result<()> {
     let opts = Dictionary::from(opts);
     let mut err = nvim::Error::new();
     unsafe {
         nvim_set_decoration_provider(
-            ns_id as Integer,
+            Integer::from(ns_id.into()),
             opts.non_owning(),
             &mut err,
         )
@@ -83,6 +83,13 @@ extern "C" {
         err: *mut Error,
     ) -> Dictionary;
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L492
+    pub(crate) fn nvim_exec_lua(
+        code: NonOwning<String>,
+        args: NonOwning<Array>,
+        err: *mut Error,
+    ) -> Object;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L235
     pub(crate) fn nvim_feedkeys(
         keys: NonOwning<String>,
@@ -143,6 +150,13 @@ extern "C" {
         error: *mut Error,
     ) -> Dictionary;
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L159
+    #[cfg(feature = "neovim-nightly")]
+    pub(crate) fn nvim_get_hl_ns(
+        opts: *const KeyDict_get_ns,
+        err: *mut Error,
+    ) -> Integer;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L109
     pub(crate) fn nvim_get_hl_id_by_name(name: NonOwning<String>) -> Integer;
 
@@ -373,4 +387,10 @@ extern "C" {
         text: NonOwning<String>,
         err: *mut Error,
     ) -> Integer;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c
+    pub(crate) fn nvim_subscribe(channel_id: u64, event: NonOwning<String>);
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c
+    pub(crate) fn nvim_unsubscribe(channel_id: u64, event: NonOwning<String>);
 }
@@ -0,0 +1,79 @@
+use crate::types::WindowConfig;
+use crate::{Error, Result, Window};
+
+/// The `zindex` Neovim gives to floating windows by default.
+const DEFAULT_BASE_ZINDEX: u32 = 50;
+
+/// A stack of floating windows kept in z-order, e.g. nested popups.
+///
+/// Every window in the stack is given a `zindex` one higher than the window
+/// below it, starting from the stack's base `zindex`. Windows that have been
+/// closed are dropped from the stack the next time it's reordered.
+#[derive(Clone, Debug)]
+pub struct FloatStack {
+    base_zindex: u32,
+    windows: Vec<Window>,
+}
+
+impl Default for FloatStack {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_ZINDEX)
+    }
+}
+
+impl FloatStack {
+    /// Creates a new empty stack whose bottom window will have a `zindex` of
+    /// `base_zindex`.
+    pub fn new(base_zindex: u32) -> Self {
+        Self { base_zindex, windows: Vec::new() }
+    }
+
+    /// Puts a floating window on top of the stack, moving it there if it was
+    /// already part of it.
+    pub fn push(&mut self, window: Window) -> Result<()> {
+        self.windows.retain(|win| win != &window);
+        self.windows.push(window);
+        self.restack()
+    }
+
+    /// Moves a window of the stack on top of all the others.
+    pub fn raise(&mut self, window: &Window) -> Result<()> {
+        if !self.windows.contains(window) {
+            return Err(Error::custom("Window is not part of the stack"));
+        }
+        self.push(window.clone())
+    }
+
+    /// Removes a window from the stack without closing it, returning whether
+    /// it was part of it.
+    pub fn remove(&mut self, window: &Window) -> bool {
+        let len = self.windows.len();
+        self.windows.retain(|win| win != window);
+        self.windows.len() != len
+    }
+
+    /// Returns the window on top of the stack, if any.
+    pub fn top(&self) -> Option<&Window> {
+        self.windows.iter().rev().find(|win| win.is_valid())
+    }
+
+    /// Returns an iterator over the windows of the stack, from the bottom to
+    /// the top.
+    pub fn windows(&self) -> impl Iterator<Item = &Window> + '_ {
+        self.windows.iter().filter(|win| win.is_valid())
+    }
+
+    /// Updates the `zindex` of every window to match its position in the
+    /// stack.
+    fn restack(&mut self) -> Result<()> {
+        self.windows.retain(Window::is_valid);
+
+        for (zindex, win) in (self.base_zindex..).zip(&mut self.windows) {
+            let config =
+                WindowConfig { zindex: Some(zindex), ..Default::default() };
+            win.set_config(&config)?;
+        }
+
+        Ok(())
+    }
+}
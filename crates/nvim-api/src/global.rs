@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use nvim_types::{
@@ -32,10 +34,23 @@ pub fn chan_send(channel_id: u32, data: &str) -> Result<()> {
 /// Binding to [`nvim_create_buf`](https://neovim.io/doc/user/api.html#nvim_create_buf()).
 ///
 /// Creates a new, empty, unnamed buffer.
+///
+/// With the `track-scratch-buffers` feature enabled the unlisted scratch
+/// buffers created by this function are recorded, and the ones that are never
+/// deleted can be retrieved with `leaked_scratch_buffers`.
 pub fn create_buf(is_listed: bool, is_scratch: bool) -> Result<Buffer> {
     let mut err = nvim::Error::new();
     let handle = unsafe { nvim_create_buf(is_listed, is_scratch, &mut err) };
-    err.into_err_or_else(|| handle.into())
+    err.into_err_or_else(|| {
+        let buf = Buffer::from(handle);
+
+        #[cfg(feature = "track-scratch-buffers")]
+        if !is_listed && is_scratch {
+            crate::scratch_buffers::track(&buf);
+        }
+
+        buf
+    })
 }
 
 /// Binding to [`nvim_create_user_command`](https://neovim.io/doc/user/api.html#nvim_create_user_command()).
@@ -97,14 +112,13 @@ pub fn del_keymap(mode: Mode, lhs: &str) -> Result<()> {
 /// Deletes an uppercase/file named mark. Returns an error if a lowercase or
 /// buffer-local named mark is used. Use [`Buffer::del_mark`] to delete a
 /// buffer-local mark.
-pub fn del_mark(name: char) -> Result<()> {
+///
+/// Returns `true` if the mark was deleted, `false` if it wasn't set.
+pub fn del_mark(name: char) -> Result<bool> {
     let name = nvim::String::from(name);
     let mut err = nvim::Error::new();
     let was_deleted = unsafe { nvim_del_mark(name.non_owning(), &mut err) };
-    err.into_err_or_flatten(|| match was_deleted {
-        true => Ok(()),
-        _ => Err(Error::custom("Couldn't delete mark")),
-    })
+    err.into_err_or_else(|| was_deleted)
 }
 
 /// Binding to [`nvim_del_user_command`](https://neovim.io/doc/user/api.html#nvim_del_user_command()).
@@ -186,6 +200,49 @@ pub fn eval_statusline(
     err.into_err_or_flatten(|| Ok(StatuslineInfos::from_obj(dict.into())?))
 }
 
+/// Binding to [`nvim_exec_lua`](https://neovim.io/doc/user/api.html#nvim_exec_lua()).
+///
+/// Executes a chunk of Lua code, passing it the `args` which can be accessed
+/// via `...`. The value returned by the chunk is converted into `T`. Useful
+/// to call Lua-only APIs which don't have a Rust binding yet.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api;
+///
+/// let n = api::exec_lua::<usize>("return #vim.lsp.get_active_clients()", [])?;
+/// ```
+pub fn exec_lua<T>(
+    code: &str,
+    args: impl IntoIterator<Item = Object>,
+) -> Result<T>
+where
+    T: FromObject,
+{
+    let args = args.into_iter().collect::<Array>();
+    let obj = self::exec_lua_object(code, args)?;
+    Ok(T::from_obj(obj)?)
+}
+
+/// Binding to [`nvim_exec_lua`](https://neovim.io/doc/user/api.html#nvim_exec_lua()).
+///
+/// Executes a chunk of Lua code, accessing the arguments via `...`. Returns
+/// the value returned by the chunk as a raw [`Object`], which can be
+/// inspected via [`Object::kind`] when its type isn't known statically.
+pub fn exec_lua_object<Args>(code: &str, args: Args) -> Result<Object>
+where
+    Args: Into<Array>,
+{
+    let code = nvim::String::from(code);
+    let args = args.into();
+    let mut err = nvim::Error::new();
+    let obj = unsafe {
+        nvim_exec_lua(code.non_owning(), args.non_owning(), &mut err)
+    };
+    err.into_err_or_else(|| obj)
+}
+
 /// Binding to [`nvim_feedkeys`](https://neovim.io/doc/user/api.html#nvim_feedkeys()).
 pub fn feedkeys(keys: &str, mode: Mode, escape_ks: bool) {
     let keys = nvim::String::from(keys);
@@ -193,6 +250,33 @@ pub fn feedkeys(keys: &str, mode: Mode, escape_ks: bool) {
     unsafe { nvim_feedkeys(keys.non_owning(), mode.non_owning(), escape_ks) }
 }
 
+/// Feeds `keys` to Neovim like [`feedkeys`] and executes them right away,
+/// only returning once the typeahead has been consumed. This means that the
+/// effects of the keys are visible as soon as the function returns.
+///
+/// Special keys have to be translated first, e.g. with
+/// [`replace_termcodes`]. Returns an error if an error message was emitted
+/// while executing the keys. `v:errmsg`, which is used to detect it, gets its
+/// previous value back before this returns.
+pub fn feedkeys_and_wait<Keys>(keys: Keys, mode: FeedkeysMode) -> Result<()>
+where
+    Keys: Into<nvim::String>,
+{
+    let _errmsg = VvarsGuard::clear(&["errmsg"])?;
+
+    let keys = keys.into();
+    let flags = nvim::String::from(mode.execute_flags());
+    unsafe { nvim_feedkeys(keys.non_owning(), flags.non_owning(), false) };
+
+    let errmsg = crate::get_vvar::<String>("errmsg")?;
+
+    if errmsg.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::custom(errmsg))
+    }
+}
+
 /// Binding to [`nvim_get_all_options_info`](https://neovim.io/doc/user/api.html#nvim_get_all_options_info()).
 ///
 /// Gets the option information for all options.
@@ -269,6 +353,13 @@ pub fn get_current_buf() -> Buffer {
     unsafe { nvim_get_current_buf() }.into()
 }
 
+/// Returns the current working directory by calling `getcwd()`. See
+/// [`set_current_dir`] to change it.
+pub fn get_current_dir() -> Result<PathBuf> {
+    crate::call_function::<_, nvim::String>("getcwd", Array::new())
+        .map(PathBuf::from)
+}
+
 /// Binding to [`nvim_get_current_line`](https://neovim.io/doc/user/api.html#nvim_get_current_line()).
 ///
 /// Gets the current line in the current bufferr.
@@ -311,6 +402,19 @@ pub fn get_hl_by_name(name: &str, rgb: bool) -> Result<HighlightInfos> {
     err.into_err_or_flatten(|| Ok(HighlightInfos::from_obj(hl.into())?))
 }
 
+/// Binding to [`nvim_get_hl_ns`](https://neovim.io/doc/user/api.html#nvim_get_hl_ns()).
+///
+/// Gets the active highlight namespace, either globally or for the window
+/// given in `opts`. Returns `-1` if no namespace is set.
+#[cfg(feature = "neovim-nightly")]
+#[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+pub fn get_hl_ns(opts: &GetHlNsOpts) -> Result<i64> {
+    let opts = KeyDict_get_ns::from(opts);
+    let mut err = nvim::Error::new();
+    let ns_id = unsafe { nvim_get_hl_ns(&opts, &mut err) };
+    err.into_err_or_else(|| ns_id)
+}
+
 /// Binding to [`nvim_get_hl_id_by_name`](https://neovim.io/doc/user/api.html#nvim_get_hl_id_by_name()).
 ///
 /// Gets a highlight definition by name.
@@ -334,10 +438,16 @@ pub fn get_keymap(mode: Mode) -> impl SuperIterator<KeymapInfos> {
 ///
 /// Returns a tuple `(row, col, buffer, buffername)` representing the position
 /// of the named mark. Marks are (1,0)-indexed.
+///
+/// Only global marks, i.e. uppercase letters and digits, are accepted. Use
+/// [`Buffer::get_mark`] to get a buffer-local mark.
 pub fn get_mark(
     name: char,
     opts: &GetMarkOpts,
 ) -> Result<(usize, usize, Buffer, String)> {
+    if !is_global_mark(name) {
+        return Err(Error::custom(format!("{name:?} is not a global mark")));
+    }
     let name = nvim::String::from(name);
     let opts = Dictionary::from(opts);
     let mut err = nvim::Error::new();
@@ -356,6 +466,13 @@ pub fn get_mark(
     })
 }
 
+/// Returns whether `name` identifies a global (i.e. uppercase or numbered)
+/// mark.
+#[inline]
+fn is_global_mark(name: char) -> bool {
+    name.is_ascii_uppercase() || name.is_ascii_digit()
+}
+
 /// Binding to [`nvim_get_mode`](https://neovim.io/doc/user/api.html#nvim_get_mode()).
 ///
 /// Gets the current mode. The [`blocking`](GotMode::blocking) field of
@@ -514,6 +631,15 @@ pub fn input_mouse(
     err.into_err_or_else(|| ())
 }
 
+/// Links the highlight group `from` to `to` in the global namespace. It's a
+/// shortcut for calling [`set_hl`] with only the `link` and `default` options
+/// set. If `default` is `true` an existing definition of `from` is not
+/// overridden, like with `:highlight default link`.
+pub fn link_hl(from: &str, to: &str, default: bool) -> Result<()> {
+    let opts = SetHighlightOpts::builder().link(to).default(default).build();
+    self::set_hl(0, from, &opts)
+}
+
 /// Binding to [`nvim_list_bufs`](https://neovim.io/doc/user/api.html#nvim_list_bufs()).
 ///
 /// Gets the current list of [`Buffer`]s, including unlisted (unloaded/deleted)
@@ -558,11 +684,13 @@ pub fn list_tabpages() -> impl SuperIterator<TabPage> {
 
 /// Binding to [`nvim_list_uis`](https://neovim.io/doc/user/api.html#nvim_list_uis()).
 ///
-/// Returns an iterator over the informations about all the attached UIs.
-pub fn list_uis() -> impl SuperIterator<UiInfos> {
+/// Returns the informations about all the attached UIs, or an error if any
+/// of them couldn't be deserialized.
+pub fn list_uis() -> Result<Vec<UiInfos>> {
     unsafe { nvim_list_uis() }
         .into_iter()
-        .map(|obj| UiInfos::from_obj(obj).unwrap())
+        .map(|obj| Ok(UiInfos::from_obj(obj)?))
+        .collect()
 }
 
 /// Binding to [`nvim_list_wins`](https://neovim.io/doc/user/api.html#nvim_list_wins()).
@@ -602,6 +730,23 @@ pub fn notify(
     err.into_err_or_else(|| ())
 }
 
+thread_local! {
+    static NOTIFIED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Same as [`notify`] but only shows a given message once per session,
+/// mirroring Lua's `vim.notify_once`. Returns whether the message was
+/// actually shown.
+pub fn notify_once(msg: &str, log_level: LogLevel) -> Result<bool> {
+    if NOTIFIED.with(|notified| notified.borrow().contains(msg)) {
+        return Ok(false);
+    }
+
+    notify(msg, log_level, &Default::default())?;
+    NOTIFIED.with(|notified| notified.borrow_mut().insert(msg.to_owned()));
+    Ok(true)
+}
+
 /// Binding to [`nvim_open_term`](https://neovim.io/doc/user/api.html#nvim_open_term()).
 ///
 /// Opens a terminal instance in a buffer. Returns the id of a channel that can
@@ -759,7 +904,9 @@ pub fn set_current_tabpage(tabpage: &TabPage) -> Result<()> {
 
 /// Binding to [`nvim_set_current_win`](https://neovim.io/doc/user/api.html#nvim_set_current_win()).
 ///
-/// Sets the current window.
+/// Sets the current window. This also works for floating windows, including
+/// the ones configured as not [`focusable`](WindowConfig::focusable), which
+/// can't be entered by user actions but can still be entered via the API.
 pub fn set_current_win(win: &Window) -> Result<()> {
     let mut err = nvim::Error::new();
     unsafe { nvim_set_current_win(win.0, &mut err) };
@@ -769,12 +916,21 @@ pub fn set_current_win(win: &Window) -> Result<()> {
 /// Binding to [`nvim_set_hl`](https://neovim.io/doc/user/api.html#nvim_set_hl()).
 ///
 /// Sets a highlight group.
-pub fn set_hl(ns_id: u32, name: &str, opts: &SetHighlightOpts) -> Result<()> {
+pub fn set_hl(
+    ns_id: impl Into<Namespace>,
+    name: &str,
+    opts: &SetHighlightOpts,
+) -> Result<()> {
     let name = nvim::String::from(name);
     let opts = KeyDict_highlight::from(opts);
     let mut err = nvim::Error::new();
     unsafe {
-        nvim_set_hl(ns_id as Integer, name.non_owning(), &opts, &mut err)
+        nvim_set_hl(
+            Integer::from(ns_id.into()),
+            name.non_owning(),
+            &opts,
+            &mut err,
+        )
     };
     err.into_err_or_else(|| ())
 }
@@ -807,6 +963,30 @@ pub fn set_keymap(
     err.into_err_or_else(|| ())
 }
 
+/// Sets several global mappings by calling [`set_keymap`] on each of them in
+/// order, e.g. to register all of a plugin's mappings at startup. This is
+/// only a convenience: the mappings aren't batched, so it's not faster than
+/// calling [`set_keymap`] in a loop.
+///
+/// The registration stops at the first mapping that can't be set, whose
+/// left-hand side is reported in the returned error. Note that the mappings
+/// before it are still set.
+pub fn set_keymaps<Mappings, Lhs, Rhs>(mappings: Mappings) -> Result<()>
+where
+    Mappings: IntoIterator<Item = (Mode, Lhs, Rhs, SetKeymapOpts)>,
+    Lhs: AsRef<str>,
+    Rhs: AsRef<str>,
+{
+    mappings.into_iter().try_for_each(|(mode, lhs, rhs, opts)| {
+        let lhs = lhs.as_ref();
+        set_keymap(mode, lhs, rhs.as_ref(), &opts).map_err(|err| {
+            Error::custom(format!(
+                "Couldn't set the keymap for {lhs:?}: {err}"
+            ))
+        })
+    })
+}
+
 /// Binding to [`nvim_set_option`](https://neovim.io/doc/user/api.html#nvim_set_option()).
 ///
 /// Sets the global value of an option.
@@ -855,6 +1035,46 @@ where
     err.into_err_or_else(|| ())
 }
 
+/// Sets the value of a buffer-local option for a specific buffer. Shorthand
+/// for [`set_option_value`] with the [`buffer`](OptionValueOptsBuilder::buffer)
+/// field of the options set to `buf`.
+#[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+)]
+pub fn set_buf_option_value<Opt>(
+    buf: &Buffer,
+    name: &str,
+    value: Opt,
+) -> Result<()>
+where
+    Opt: ToObject,
+{
+    let opts = OptionValueOpts::builder().buffer(buf.clone()).build();
+    self::set_option_value(name, value, &opts)
+}
+
+/// Sets the value of a window-local option for a specific window. Shorthand
+/// for [`set_option_value`] with the [`window`](OptionValueOptsBuilder::window)
+/// field of the options set to `win`.
+#[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
+)]
+pub fn set_win_option_value<Opt>(
+    win: &Window,
+    name: &str,
+    value: Opt,
+) -> Result<()>
+where
+    Opt: ToObject,
+{
+    let opts = OptionValueOpts::builder().window(win.clone()).build();
+    self::set_option_value(name, value, &opts)
+}
+
 /// Binding to [`nvim_set_var`](https://neovim.io/doc/user/api.html#nvim_set_var()).
 ///
 /// Sets a global (`g:`) variable.
@@ -893,3 +1113,132 @@ pub fn strwidth(text: &str) -> Result<usize> {
     let width = unsafe { nvim_strwidth(text.non_owning(), &mut err) };
     err.into_err_or_else(|| width.try_into().expect("always positive"))
 }
+
+/// Binding to [`nvim_subscribe`](https://neovim.io/doc/user/api.html#nvim_subscribe()).
+///
+/// Subscribes the RPC channel `channel_id` to the `event` broadcast, i.e. to
+/// the notifications sent with `rpcnotify(0, event, ...)`. The notifications
+/// are delivered to the client on the other side of the channel, not to the
+/// plugin.
+///
+/// Unlike the other API functions this can't act on behalf of the plugin,
+/// which isn't an RPC client, so it takes the channel to subscribe. That's
+/// usually one opened with the `rpc` option of `sockconnect()` or
+/// `jobstart()`. Returns an error if `channel_id` is not an RPC channel.
+pub fn subscribe(channel_id: u32, event: &str) -> Result<()> {
+    self::check_rpc_channel(channel_id)?;
+    let event = nvim::String::from(event);
+    unsafe { nvim_subscribe(channel_id.into(), event.non_owning()) };
+    Ok(())
+}
+
+/// Binding to [`nvim_unsubscribe`](https://neovim.io/doc/user/api.html#nvim_unsubscribe()).
+///
+/// Unsubscribes the RPC channel `channel_id` from the `event` broadcast.
+///
+/// Returns an error if `channel_id` is not an RPC channel.
+pub fn unsubscribe(channel_id: u32, event: &str) -> Result<()> {
+    self::check_rpc_channel(channel_id)?;
+    let event = nvim::String::from(event);
+    unsafe { nvim_unsubscribe(channel_id.into(), event.non_owning()) };
+    Ok(())
+}
+
+/// Sets the global value of each option in `options` while running `f`,
+/// restoring the previous values afterwards. The options are restored even if
+/// `f` returns an error or panics, in which case that error (or panic) takes
+/// precedence over any error raised while restoring them.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api;
+///
+/// // Run some commands without triggering any autocommand.
+/// api::with_options(&[("eventignore", "all".into())], || {
+///     api::command("bufdo normal! gg=G")
+/// })?;
+/// ```
+pub fn with_options<R>(
+    options: &[(&str, Object)],
+    f: impl FnOnce() -> Result<R>,
+) -> Result<R> {
+    let mut guard = OptionsGuard(Vec::with_capacity(options.len()));
+
+    for (name, value) in options {
+        let old = {
+            let name = nvim::String::from(*name);
+            let mut err = nvim::Error::new();
+            let obj = unsafe { nvim_get_option(name.non_owning(), &mut err) };
+            err.into_err_or_else::<_, Error, _>(|| obj)?
+        };
+        self::set_option(name, value.clone())?;
+        guard.0.push((name, old));
+    }
+
+    let res = f();
+    let restored = guard.restore();
+    let ret = res?;
+    restored.map(|()| ret)
+}
+
+/// Neovim aborts if `nvim_subscribe` or `nvim_unsubscribe` are called with
+/// a channel that's not an RPC channel, so we check that first.
+fn check_rpc_channel(channel_id: u32) -> Result<()> {
+    match get_chan_info(channel_id) {
+        Ok(infos) if infos.mode == ChannelMode::Rpc => Ok(()),
+        _ => Err(Error::custom(format!(
+            "{channel_id} is not a valid RPC channel"
+        ))),
+    }
+}
+
+/// The previous values of the options set by [`with_options`], restored when
+/// the guard is dropped.
+struct OptionsGuard<'a>(Vec<(&'a str, Object)>);
+
+impl OptionsGuard<'_> {
+    /// Restores the options in reverse order, so that an option set more than
+    /// once gets back its original value. Returns the first error.
+    fn restore(&mut self) -> Result<()> {
+        let mut res = Ok(());
+        while let Some((name, value)) = self.0.pop() {
+            let restored = self::set_option(name, value);
+            if res.is_ok() {
+                res = restored;
+            }
+        }
+        res
+    }
+}
+
+impl Drop for OptionsGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+/// The previous values of some string `v:` variables, restored when the guard
+/// is dropped.
+pub(crate) struct VvarsGuard(Vec<(&'static str, String)>);
+
+impl VvarsGuard {
+    /// Saves the current values of the `v:` variables and clears them.
+    pub(crate) fn clear(names: &[&'static str]) -> Result<Self> {
+        let mut guard = Self(Vec::with_capacity(names.len()));
+        for &name in names {
+            let old = self::get_vvar::<String>(name)?;
+            self::set_vvar(name, "")?;
+            guard.0.push((name, old));
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for VvarsGuard {
+    fn drop(&mut self) {
+        for (name, value) in self.0.drain(..) {
+            let _ = self::set_vvar(name, value);
+        }
+    }
+}
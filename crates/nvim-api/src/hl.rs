@@ -0,0 +1,254 @@
+//! Names of the standard highlight groups, to be used wherever a highlight
+//! group is accepted (e.g. [`Buffer::add_highlight`](crate::Buffer::add_highlight),
+//! [`api::set_hl`](crate::set_hl) or the `hl_group` of an extmark) instead of
+//! stringly-typed names.
+//!
+//! The groups used by the editor UI are described in `:h highlight-groups`,
+//! the ones used by syntax highlighting in `:h group-name`.
+
+// UI groups, see `:h highlight-groups`.
+
+/// Columns set with `colorcolumn`.
+pub const COLOR_COLUMN: &str = "ColorColumn";
+
+/// Placeholder characters substituted for concealed text.
+pub const CONCEAL: &str = "Conceal";
+
+/// Character under the cursor.
+pub const CURSOR: &str = "Cursor";
+
+/// Screen column at the cursor, when `cursorcolumn` is set.
+pub const CURSOR_COLUMN: &str = "CursorColumn";
+
+/// Screen line at the cursor, when `cursorline` is set.
+pub const CURSOR_LINE: &str = "CursorLine";
+
+/// Line number of the cursor line.
+pub const CURSOR_LINE_NR: &str = "CursorLineNr";
+
+/// Diff mode: added line.
+pub const DIFF_ADD: &str = "DiffAdd";
+
+/// Diff mode: changed line.
+pub const DIFF_CHANGE: &str = "DiffChange";
+
+/// Diff mode: deleted line.
+pub const DIFF_DELETE: &str = "DiffDelete";
+
+/// Diff mode: changed text within a changed line.
+pub const DIFF_TEXT: &str = "DiffText";
+
+/// Directory names and other special names in listings.
+pub const DIRECTORY: &str = "Directory";
+
+/// Error messages on the command line.
+pub const ERROR_MSG: &str = "ErrorMsg";
+
+/// Border of floating windows.
+pub const FLOAT_BORDER: &str = "FloatBorder";
+
+/// The fold column.
+pub const FOLD_COLUMN: &str = "FoldColumn";
+
+/// Line used for closed folds.
+pub const FOLDED: &str = "Folded";
+
+/// The `incsearch` highlighting.
+pub const INC_SEARCH: &str = "IncSearch";
+
+/// Line numbers.
+pub const LINE_NR: &str = "LineNr";
+
+/// Paired bracket under the cursor.
+pub const MATCH_PAREN: &str = "MatchParen";
+
+/// The mode message, e.g. `-- INSERT --`.
+pub const MODE_MSG: &str = "ModeMsg";
+
+/// The more-prompt.
+pub const MORE_MSG: &str = "MoreMsg";
+
+/// Filler characters like `~` and `@` at the end of the window.
+pub const NON_TEXT: &str = "NonText";
+
+/// Normal text.
+pub const NORMAL: &str = "Normal";
+
+/// Normal text in floating windows.
+pub const NORMAL_FLOAT: &str = "NormalFloat";
+
+/// Normal text in non-current windows.
+pub const NORMAL_NC: &str = "NormalNC";
+
+/// Popup menu: normal item.
+pub const PMENU: &str = "Pmenu";
+
+/// Popup menu: scrollbar.
+pub const PMENU_SBAR: &str = "PmenuSbar";
+
+/// Popup menu: selected item.
+pub const PMENU_SEL: &str = "PmenuSel";
+
+/// Popup menu: thumb of the scrollbar.
+pub const PMENU_THUMB: &str = "PmenuThumb";
+
+/// Hit-enter prompt and yes/no questions.
+pub const QUESTION: &str = "Question";
+
+/// Last search pattern highlighting.
+pub const SEARCH: &str = "Search";
+
+/// Column where signs are displayed.
+pub const SIGN_COLUMN: &str = "SignColumn";
+
+/// Words not recognized by the spellchecker.
+pub const SPELL_BAD: &str = "SpellBad";
+
+/// Words that should start with a capital letter.
+pub const SPELL_CAP: &str = "SpellCap";
+
+/// Words only used in another region.
+pub const SPELL_LOCAL: &str = "SpellLocal";
+
+/// Words that are hardly ever used.
+pub const SPELL_RARE: &str = "SpellRare";
+
+/// Status line of the current window.
+pub const STATUS_LINE: &str = "StatusLine";
+
+/// Status lines of non-current windows.
+pub const STATUS_LINE_NC: &str = "StatusLineNC";
+
+/// Tab pages line, not active tab page label.
+pub const TAB_LINE: &str = "TabLine";
+
+/// Tab pages line, where there are no labels.
+pub const TAB_LINE_FILL: &str = "TabLineFill";
+
+/// Tab pages line, active tab page label.
+pub const TAB_LINE_SEL: &str = "TabLineSel";
+
+/// Titles for output of `:set all`, `:autocmd` etc.
+pub const TITLE: &str = "Title";
+
+/// Visual mode selection.
+pub const VISUAL: &str = "Visual";
+
+/// Warning messages.
+pub const WARNING_MSG: &str = "WarningMsg";
+
+/// Characters shown by `listchars` like `space` and `tab`.
+pub const WHITESPACE: &str = "Whitespace";
+
+/// Current match in the wildmenu completion.
+pub const WILD_MENU: &str = "WildMenu";
+
+/// Separators between window splits.
+pub const WIN_SEPARATOR: &str = "WinSeparator";
+
+// Syntax groups, see `:h group-name`.
+
+/// Any comment.
+pub const COMMENT: &str = "Comment";
+
+/// Any constant.
+pub const CONSTANT: &str = "Constant";
+
+/// A string constant.
+pub const STRING: &str = "String";
+
+/// A character constant.
+pub const CHARACTER: &str = "Character";
+
+/// A number constant.
+pub const NUMBER: &str = "Number";
+
+/// A boolean constant.
+pub const BOOLEAN: &str = "Boolean";
+
+/// A floating point constant.
+pub const FLOAT: &str = "Float";
+
+/// Any variable name.
+pub const IDENTIFIER: &str = "Identifier";
+
+/// Function names.
+pub const FUNCTION: &str = "Function";
+
+/// Any statement.
+pub const STATEMENT: &str = "Statement";
+
+/// `if`, `then`, `else`, `switch`, etc.
+pub const CONDITIONAL: &str = "Conditional";
+
+/// `for`, `do`, `while`, etc.
+pub const REPEAT: &str = "Repeat";
+
+/// `case`, `default`, etc.
+pub const LABEL: &str = "Label";
+
+/// `sizeof`, `+`, `*`, etc.
+pub const OPERATOR: &str = "Operator";
+
+/// Any other keyword.
+pub const KEYWORD: &str = "Keyword";
+
+/// `try`, `catch`, `throw`.
+pub const EXCEPTION: &str = "Exception";
+
+/// Generic preprocessor.
+pub const PRE_PROC: &str = "PreProc";
+
+/// Preprocessor `#include`.
+pub const INCLUDE: &str = "Include";
+
+/// Preprocessor `#define`.
+pub const DEFINE: &str = "Define";
+
+/// Same as `Define`.
+pub const MACRO: &str = "Macro";
+
+/// Preprocessor `#if`, `#else`, `#endif`, etc.
+pub const PRE_CONDIT: &str = "PreCondit";
+
+/// `int`, `long`, `char`, etc.
+pub const TYPE: &str = "Type";
+
+/// `static`, `register`, `volatile`, etc.
+pub const STORAGE_CLASS: &str = "StorageClass";
+
+/// `struct`, `union`, `enum`, etc.
+pub const STRUCTURE: &str = "Structure";
+
+/// A `typedef`.
+pub const TYPEDEF: &str = "Typedef";
+
+/// Any special symbol.
+pub const SPECIAL: &str = "Special";
+
+/// Special character in a constant.
+pub const SPECIAL_CHAR: &str = "SpecialChar";
+
+/// You can use `CTRL-]` on this.
+pub const TAG: &str = "Tag";
+
+/// Character that needs attention.
+pub const DELIMITER: &str = "Delimiter";
+
+/// Special things inside a comment.
+pub const SPECIAL_COMMENT: &str = "SpecialComment";
+
+/// Debugging statements.
+pub const DEBUG: &str = "Debug";
+
+/// Text that stands out, e.g. HTML links.
+pub const UNDERLINED: &str = "Underlined";
+
+/// Left blank, hidden.
+pub const IGNORE: &str = "Ignore";
+
+/// Any erroneous construct.
+pub const ERROR: &str = "Error";
+
+/// Anything that needs extra attention, e.g. `TODO` and `FIXME`.
+pub const TODO: &str = "Todo";
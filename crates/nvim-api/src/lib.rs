@@ -11,29 +11,53 @@
 //! `nvim_tabpage_*` are implemented as methods on the [`Buffer`], [`Window`]
 //! and [`TabPage`] objects respectively.
 
+mod attach_guard;
 mod autocmd;
 mod buffer;
+mod buffer_ops;
 mod error;
 mod extmark;
 mod ffi;
+mod float_stack;
 mod global;
+pub mod hl;
 pub(crate) mod iterator;
+mod line_diff;
+mod lua_chunk;
 pub mod opts;
+mod scratch_buffer;
+#[cfg(feature = "track-scratch-buffers")]
+mod scratch_buffers;
 pub(crate) mod serde_utils;
+mod sidebar_window;
 mod tabpage;
 mod trait_utils;
 pub mod types;
 mod vimscript;
+mod virt_text_layer;
 mod win_config;
 mod window;
 
+pub use attach_guard::*;
 pub use autocmd::*;
 pub use buffer::*;
+pub use buffer_ops::*;
 pub use error::{Error, Result};
 pub use extmark::*;
+pub use float_stack::*;
 pub use global::*;
+pub use lua_chunk::*;
+pub use scratch_buffer::*;
+#[cfg(feature = "track-scratch-buffers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "track-scratch-buffers")))]
+pub use scratch_buffers::{
+    leaked_scratch_buffers,
+    report_leaked_scratch_buffers,
+};
+pub use sidebar_window::*;
 pub use tabpage::*;
 pub use vimscript::*;
+pub use virt_text_layer::*;
 pub use win_config::*;
 pub use window::*;
 
@@ -0,0 +1,164 @@
+use std::ops::Range;
+
+/// A run of lines in the old contents that has to be replaced by a run of
+/// lines in the new contents. Either range can be empty, for pure insertions
+/// and deletions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Hunk {
+    pub(crate) old: Range<usize>,
+    pub(crate) new: Range<usize>,
+}
+
+/// Computes the minimal set of hunks turning `old` into `new` using Myers'
+/// diff algorithm, in increasing order.
+pub(crate) fn diff<T, U>(old: &[T], new: &[U]) -> Vec<Hunk>
+where
+    T: PartialEq<U>,
+{
+    let (n, m) = (old.len() as isize, new.len() as isize);
+
+    // `v[k]` is the furthest x reached on the diagonal `k = x - y`, offset so
+    // that negative diagonals can be indexed. Before each step we save the
+    // diagonals the backtracking will need to look at.
+    let offset = n + m + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    let mut trace = Vec::<Vec<isize>>::new();
+
+    'search: for d in 0..=(n + m) {
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[i] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk the path back from the end, collecting the pairs of equal lines.
+    let mut matches = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().skip(1).rev() {
+        let d = d as isize;
+        let k = x - y;
+
+        // `v` holds the diagonals from `-d` to `d`.
+        let at = |k: isize| v[(k + d) as usize];
+
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    // Whatever is left is the initial snake, made only of equal lines.
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        matches.push((x as usize, y as usize));
+    }
+
+    matches.reverse();
+    matches.push((n as usize, m as usize));
+
+    let mut hunks = Vec::new();
+    let (mut old_start, mut new_start) = (0, 0);
+
+    for (x, y) in matches {
+        if x > old_start || y > new_start {
+            hunks.push(Hunk { old: old_start..x, new: new_start..y });
+        }
+        old_start = x + 1;
+        new_start = y + 1;
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old: Range<usize>, new: Range<usize>) -> Hunk {
+        Hunk { old, new }
+    }
+
+    /// Applies the hunks bottom-up, like `Buffer::set_lines_diff` does.
+    fn apply(old: &[&str], new: &[&str]) -> Vec<String> {
+        let mut lines = old.iter().map(|&l| l.to_owned()).collect::<Vec<_>>();
+        for Hunk { old, new: range } in diff(old, new).into_iter().rev() {
+            lines.splice(old, new[range].iter().map(|&l| l.to_owned()));
+        }
+        lines
+    }
+
+    #[test]
+    fn equal() {
+        assert_eq!(Vec::<Hunk>::new(), diff(&["a", "b"], &["a", "b"]));
+        assert_eq!(Vec::<Hunk>::new(), diff::<&str, &str>(&[], &[]));
+    }
+
+    #[test]
+    fn from_and_to_empty() {
+        let empty: [&str; 0] = [];
+        assert_eq!(vec![hunk(0..0, 0..2)], diff(&empty, &["a", "b"]));
+        assert_eq!(vec![hunk(0..2, 0..0)], diff(&["a", "b"], &empty));
+    }
+
+    #[test]
+    fn edit_and_insertion() {
+        let old = ["a", "b", "c", "d", "e"];
+        let new = ["a", "B", "c", "d", "x", "e"];
+        assert_eq!(vec![hunk(1..2, 1..2), hunk(4..4, 4..5)], diff(&old, &new));
+        assert_eq!(new.to_vec(), apply(&old, &new));
+    }
+
+    #[test]
+    fn deletions() {
+        let old = ["a", "b", "c", "d", "e"];
+        let new = ["b", "c", "e"];
+        assert_eq!(vec![hunk(0..1, 0..0), hunk(3..4, 2..2)], diff(&old, &new));
+        assert_eq!(new.to_vec(), apply(&old, &new));
+    }
+
+    #[test]
+    fn minimal() {
+        let old = ["a", "b", "c", "a", "b", "b", "a"];
+        let new = ["c", "b", "a", "b", "a", "c"];
+        let hunks = diff(&old, &new);
+        let changed =
+            hunks.iter().map(|h| h.old.len() + h.new.len()).sum::<usize>();
+        assert_eq!(5, changed);
+        assert_eq!(new.to_vec(), apply(&old, &new));
+    }
+}
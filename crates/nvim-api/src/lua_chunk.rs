@@ -0,0 +1,57 @@
+use std::ffi::c_char;
+
+use luajit_bindings::{self as lua, ffi, macros::cstr, Poppable, Pushable};
+use nvim_types::LuaFunction;
+
+use crate::{Error, Result};
+
+/// A chunk of Lua code compiled once and stored in the Lua registry. Unlike
+/// [`exec_lua`](crate::exec_lua), which parses the code every time it's
+/// called, a `LuaChunk` can be run many times at the cost of a single
+/// function call.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::LuaChunk;
+///
+/// let add = LuaChunk::compile("local a, b = ...; return a + b")?;
+/// assert_eq!(3, add.call::<_, usize>((1, 2))?);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LuaChunk(LuaFunction);
+
+impl LuaChunk {
+    /// Compiles `code` without running it. Returns an error containing Lua's
+    /// message if the code isn't valid Lua.
+    pub fn compile(code: &str) -> Result<Self> {
+        unsafe {
+            lua::with_state(|lstate| {
+                let status = ffi::luaL_loadbuffer(
+                    lstate,
+                    code.as_ptr() as *const c_char,
+                    code.len(),
+                    cstr!("=LuaChunk"),
+                );
+
+                if status != ffi::LUA_OK {
+                    // The error message is left on the stack.
+                    let msg = <String as Poppable>::pop(lstate)?;
+                    return Err(Error::custom(msg));
+                }
+
+                Ok(Self(LuaFunction::pop(lstate)?))
+            })
+        }
+    }
+
+    /// Runs the chunk, passing it the `args` which can be accessed via `...`,
+    /// and pops its return value as an `R`.
+    pub fn call<A, R>(&self, args: A) -> Result<R>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        Ok(self.0.call(args)?)
+    }
+}
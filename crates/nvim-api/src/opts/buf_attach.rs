@@ -142,7 +142,11 @@ impl BufAttachOptsBuilder {
         self
     }
 
-    /// Callback invoked on detach.
+    /// Callback invoked on detach, e.g. when the buffer is unloaded or wiped
+    /// out. It's not invoked when the callbacks detach by returning `true`
+    /// or when the [`AttachGuard`](crate::AttachGuard) returned by
+    /// [`Buffer::attach_guarded`] is dropped, see
+    /// [`Buffer::on_wipe`] for a callback that always runs.
     pub fn on_detach<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnDetachArgs, ShouldDetach>,
@@ -0,0 +1,25 @@
+use derive_builder::Builder;
+
+/// Options passed to [`api::complete`](crate::complete).
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct CompleteOpts {
+    /// If `true` the items are added to the current list of matches with
+    /// [`complete_add()`](https://neovim.io/doc/user/builtin.html#complete_add())
+    /// instead of replacing it, e.g. from a `completefunc`. The start column
+    /// is ignored in that case.
+    pub(crate) append: bool,
+}
+
+impl CompleteOpts {
+    #[inline(always)]
+    pub fn builder() -> CompleteOptsBuilder {
+        CompleteOptsBuilder::default()
+    }
+}
+
+impl CompleteOptsBuilder {
+    pub fn build(&mut self) -> CompleteOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
@@ -69,10 +69,22 @@ macro_rules! object_setter {
 
 impl CreateCommandOptsBuilder {
     object_setter!(addr, CommandAddr);
-    object_setter!(complete, CommandComplete);
     object_setter!(nargs, CommandNArgs);
     object_setter!(range, CommandRange);
 
+    /// Completion for the command's arguments. Besides a
+    /// [`CommandComplete`], this also accepts a closure
+    /// `Fn(&str, &str, usize) -> Result<Vec<String>>` computing the
+    /// candidates from the argument being completed, the whole command line
+    /// and the cursor position. See `:h command-completion-customlist`.
+    pub fn complete(
+        &mut self,
+        complete: impl Into<CommandComplete>,
+    ) -> &mut Self {
+        self.complete = Some(complete.into().to_obj().unwrap());
+        self
+    }
+
     /// Description for the command.
     pub fn desc(&mut self, desc: impl Into<nvim::String>) -> &mut Self {
         self.desc = Some(desc.into().into());
@@ -1,7 +1,9 @@
 use derive_builder::Builder;
-use nvim_types::{Dictionary, Object};
+use nvim_types::{Dictionary, Function, Object};
 
+use super::SetExtmarkOpts;
 use crate::trait_utils::ToFunction;
+use crate::types::Namespace;
 use crate::{Buffer, Window};
 
 // NOTE: docs say a third argument of changedtick is passed. I don't see it.
@@ -46,6 +48,11 @@ pub type OnWinArgs = (
     u32,    // botline guess
 );
 
+/// A list of `(col, hl_group)` pairs returned by the function registered to
+/// [`on_line_highlights`](DecorationProviderOptsBuilder::on_line_highlights),
+/// each one highlighting a single (0-indexed, byte) column of the line.
+pub type OnLineHighlights = Vec<(usize, String)>;
+
 /// The `on_start` callback can return `false` to disable the provider until
 /// the next redraw.
 pub type DontSkipRedrawCycle = bool;
@@ -108,6 +115,54 @@ impl DecorationProviderOptsBuilder {
         self
     }
 
+    /// Like [`on_line`](DecorationProviderOptsBuilder::on_line), but the
+    /// callback returns the highlights to apply to the line instead of
+    /// setting them itself. The highlights are sorted by column, and each run
+    /// of adjacent columns with the same highlight group is applied with a
+    /// single ephemeral extmark in the `ns_id` namespace, which should be the
+    /// one the provider is registered for.
+    pub fn on_line_highlights<F>(
+        &mut self,
+        ns_id: impl Into<Namespace>,
+        mut fun: F,
+    ) -> &mut Self
+    where
+        F: FnMut(OnLineArgs) -> crate::Result<OnLineHighlights> + 'static,
+    {
+        let ns_id = ns_id.into();
+
+        let on_line = move |args: OnLineArgs| {
+            let (_, _, mut buf, row) = args.clone();
+
+            let mut hls = fun(args)?;
+            hls.sort_by_key(|&(col, _)| col);
+            let mut hls = hls.into_iter().peekable();
+
+            while let Some((start, hl_group)) = hls.next() {
+                let mut end = start + 1;
+
+                while let Some((col, _)) =
+                    hls.next_if(|(col, hl)| *col <= end && *hl == hl_group)
+                {
+                    end = end.max(col + 1);
+                }
+
+                let opts = SetExtmarkOpts::builder()
+                    .end_col(end)
+                    .hl_group(&hl_group)
+                    .ephemeral(true)
+                    .build();
+
+                buf.set_extmark(ns_id, row, start, &opts)?;
+            }
+
+            Ok::<_, crate::Error>(())
+        };
+
+        self.on_line = Some(Function::from_fn_mut(on_line).into());
+        self
+    }
+
     pub fn on_start<F>(&mut self, fun: F) -> &mut Self
     where
         F: ToFunction<OnStartArgs, DontSkipRedrawCycle>,
@@ -33,11 +33,11 @@ pub struct EvalStatuslineOpts {
         doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
     )]
     #[builder(setter(strip_option))]
-    use_winbar: Option<bool>,
+    pub(crate) use_winbar: Option<bool>,
 
     /// Window to use as context for the statusline.
     #[builder(setter(into, strip_option))]
-    window: Option<Window>,
+    pub(crate) window: Option<Window>,
 }
 
 impl EvalStatuslineOpts {
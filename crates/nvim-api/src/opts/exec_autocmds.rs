@@ -1,5 +1,5 @@
 use derive_builder::Builder;
-use nvim_types::{NonOwning, Object};
+use nvim_types::{NonOwning, Object, ToObjectError};
 
 use crate::trait_utils::StringOrInt;
 use crate::Buffer;
@@ -18,7 +18,13 @@ pub struct ExecAutocmdsOpts {
         docsrs,
         doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
     )]
-    #[builder(setter(custom))]
+    #[builder(
+        setter(custom),
+        field(
+            type = "Option<Result<Object, ToObjectError>>",
+            build = "self.data.clone().and_then(Result::ok).unwrap_or_default()"
+        )
+    )]
     data: Object,
 
     #[builder(setter(custom))]
@@ -39,13 +45,20 @@ impl ExecAutocmdsOpts {
 }
 
 impl ExecAutocmdsOptsBuilder {
+    /// Arbitrary data to send to the autocommand callbacks, which can read it
+    /// back from the `data` field of
+    /// [`AutocmdCallbackArgs`](crate::types::AutocmdCallbackArgs) with
+    /// [`FromObject`](nvim_types::FromObject).
+    ///
+    /// If `data` can't be converted into an [`Object`] the error is returned
+    /// by [`build`](ExecAutocmdsOptsBuilder::build).
     #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
     #[cfg_attr(
         docsrs,
         doc(cfg(any(feature = "neovim-0-8", feature = "neovim-nightly")))
     )]
-    pub fn data(&mut self, any: impl Into<Object>) -> &mut Self {
-        self.data = Some(any.into());
+    pub fn data<T: nvim_types::ToObject>(&mut self, data: T) -> &mut Self {
+        self.data = Some(data.to_obj());
         self
     }
 
@@ -79,8 +92,18 @@ impl ExecAutocmdsOptsBuilder {
         self
     }
 
-    pub fn build(&mut self) -> ExecAutocmdsOpts {
-        self.fallible_build().expect("never fails, all fields have defaults")
+    /// Builds the options, failing if the value passed to
+    /// [`data`](ExecAutocmdsOptsBuilder::data) couldn't be converted into an
+    /// [`Object`].
+    pub fn build(&mut self) -> Result<ExecAutocmdsOpts, ToObjectError> {
+        #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+        if let Some(Err(err)) = &self.data {
+            return Err(err.clone());
+        }
+
+        Ok(self
+            .fallible_build()
+            .expect("never fails, all fields have defaults"))
     }
 }
 
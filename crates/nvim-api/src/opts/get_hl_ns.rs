@@ -0,0 +1,40 @@
+use derive_builder::Builder;
+use nvim_types::Object;
+
+use crate::Window;
+
+/// Options passed to [`api::get_hl_ns`](crate::get_hl_ns).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Builder)]
+#[builder(default, build_fn(private, name = "fallible_build"))]
+pub struct GetHlNsOpts {
+    /// Get the highlight namespace of this window instead of the global one.
+    #[builder(setter(into, strip_option))]
+    window: Option<Window>,
+}
+
+impl GetHlNsOpts {
+    #[inline(always)]
+    /// Creates a new [`GetHlNsOptsBuilder`].
+    pub fn builder() -> GetHlNsOptsBuilder {
+        GetHlNsOptsBuilder::default()
+    }
+}
+
+impl GetHlNsOptsBuilder {
+    pub fn build(&mut self) -> GetHlNsOpts {
+        self.fallible_build().expect("never fails, all fields have defaults")
+    }
+}
+
+#[derive(Default)]
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub(crate) struct KeyDict_get_ns {
+    winid: Object,
+}
+
+impl From<&GetHlNsOpts> for KeyDict_get_ns {
+    fn from(opts: &GetHlNsOpts) -> Self {
+        Self { winid: opts.window.as_ref().into() }
+    }
+}
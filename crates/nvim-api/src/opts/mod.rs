@@ -2,6 +2,7 @@ mod buf_attach;
 mod buf_delete;
 mod clear_autocmds;
 mod cmd;
+mod complete;
 mod create_augroup;
 mod create_autocmd;
 mod create_command;
@@ -13,6 +14,8 @@ mod get_commands;
 mod get_context;
 mod get_extmark_by_id;
 mod get_extmarks;
+#[cfg(feature = "neovim-nightly")]
+mod get_hl_ns;
 mod get_mark;
 mod get_option_value;
 mod get_text;
@@ -29,6 +32,7 @@ pub use buf_attach::*;
 pub use buf_delete::*;
 pub use clear_autocmds::*;
 pub use cmd::*;
+pub use complete::*;
 pub use create_augroup::*;
 pub use create_autocmd::*;
 pub use create_command::*;
@@ -40,6 +44,8 @@ pub use get_commands::*;
 pub use get_context::*;
 pub use get_extmark_by_id::*;
 pub use get_extmarks::*;
+#[cfg(feature = "neovim-nightly")]
+pub use get_hl_ns::*;
 pub use get_mark::*;
 pub use get_option_value::*;
 pub use get_text::*;
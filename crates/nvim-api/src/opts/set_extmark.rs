@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use nvim_types::{self as nvim, Array, Integer, Object};
 
-use crate::types::{ExtmarkHlMode, ExtmarkVirtTextPosition};
+use crate::types::{ExtmarkHlMode, ExtmarkId, ExtmarkVirtTextPosition};
 
 #[derive(Clone, Debug, Default)]
 #[allow(non_camel_case_types)]
@@ -97,8 +97,8 @@ impl SetExtmarkOpts {
     }
 
     #[inline(always)]
-    pub fn set_id(&mut self, id: u32) {
-        self.0.id = id.into();
+    pub fn set_id(&mut self, id: impl Into<ExtmarkId>) {
+        self.0.id = id.into().into();
     }
 
     #[inline(always)]
@@ -289,9 +289,11 @@ impl SetExtmarkOptsBuilder {
         self
     }
 
-    /// Id of the extmark to edit.
+    /// Id of the extmark to edit. If an extmark with this id already exists
+    /// in the namespace it's updated in place, otherwise one is created with
+    /// this id.
     #[inline(always)]
-    pub fn id(&mut self, id: u32) -> &mut Self {
+    pub fn id(&mut self, id: impl Into<ExtmarkId>) -> &mut Self {
         self.set_id(id);
         self
     }
@@ -1,6 +1,8 @@
 use derive_builder::Builder;
 use nvim_types::{self as nvim, NonOwning, Object};
 
+use crate::types::Color;
+
 /// Options passed to [`nvim_oxi::api::set_hl`](crate::set_hl).
 #[derive(Clone, Debug, Default, PartialEq, Builder)]
 #[builder(default, build_fn(private, name = "fallible_build"))]
@@ -75,8 +77,8 @@ impl SetHighlightOpts {
 }
 
 impl SetHighlightOptsBuilder {
-    pub fn background(&mut self, background: &str) -> &mut Self {
-        self.background = Some(nvim::String::from(background).into());
+    pub fn background(&mut self, background: impl Into<Color>) -> &mut Self {
+        self.background = Some(Object::from(background.into()));
         self
     }
 
@@ -95,8 +97,8 @@ impl SetHighlightOptsBuilder {
         self
     }
 
-    pub fn foreground(&mut self, foreground: &str) -> &mut Self {
-        self.foreground = Some(nvim::String::from(foreground).into());
+    pub fn foreground(&mut self, foreground: impl Into<Color>) -> &mut Self {
+        self.foreground = Some(Object::from(foreground.into()));
         self
     }
 
@@ -105,8 +107,8 @@ impl SetHighlightOptsBuilder {
         self
     }
 
-    pub fn special(&mut self, special: &str) -> &mut Self {
-        self.special = Some(nvim::String::from(special).into());
+    pub fn special(&mut self, special: impl Into<Color>) -> &mut Self {
+        self.special = Some(Object::from(special.into()));
         self
     }
 
@@ -0,0 +1,75 @@
+use derive_builder::Builder;
+
+use crate::types::BufType;
+use crate::{Buffer, Result};
+
+/// The options of a scratch buffer created via [`create_buf`], set all at
+/// once by [`ScratchBufferBuilder::build`].
+///
+/// The defaults match the ones of `nvim_create_buf` with `scratch` set, i.e.
+/// an unlisted `nofile` buffer that's hidden when abandoned and has no swap
+/// file.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::ScratchBuffer;
+///
+/// let buf = ScratchBuffer::builder().filetype("markdown").build()?;
+/// ```
+///
+/// [`create_buf`]: crate::create_buf
+#[derive(Clone, Debug, Builder)]
+#[builder(build_fn(private, name = "fallible_build"))]
+pub struct ScratchBuffer {
+    /// Value of the `bufhidden` option.
+    #[builder(setter(into), default = "\"hide\".into()")]
+    bufhidden: String,
+
+    /// Value of the `buftype` option.
+    #[builder(default = "BufType::Nofile")]
+    buftype: BufType,
+
+    /// Value of the `filetype` option, left empty if not set.
+    #[builder(setter(into, strip_option), default)]
+    filetype: Option<String>,
+
+    /// Whether the buffer is listed, i.e. shows up in `:ls`.
+    #[builder(default)]
+    listed: bool,
+
+    /// Value of the `swapfile` option.
+    #[builder(default)]
+    swapfile: bool,
+}
+
+impl ScratchBuffer {
+    #[inline(always)]
+    pub fn builder() -> ScratchBufferBuilder {
+        ScratchBufferBuilder::default()
+    }
+}
+
+impl ScratchBufferBuilder {
+    /// Creates the buffer and sets its options.
+    ///
+    /// The options are set before the buffer is displayed in any window.
+    /// `filetype` is set last so that the `FileType` autocommands and
+    /// ftplugins triggered by it already see the other options.
+    pub fn build(&mut self) -> Result<Buffer> {
+        let opts = self
+            .fallible_build()
+            .expect("never fails, all fields have defaults");
+
+        let mut buf = crate::create_buf(opts.listed, true)?;
+        buf.set_buftype(opts.buftype)?;
+        buf.set_option("bufhidden", opts.bufhidden)?;
+        buf.set_option("swapfile", opts.swapfile)?;
+
+        if let Some(filetype) = opts.filetype {
+            buf.set_filetype(&filetype)?;
+        }
+
+        Ok(buf)
+    }
+}
@@ -0,0 +1,83 @@
+//! Opt-in tracking of the scratch buffers created via [`create_buf`], used to
+//! debug plugins that forget to delete them.
+//!
+//! The leaked buffers are reported automatically when Neovim exits, and can
+//! also be checked at any time with [`leaked_scratch_buffers`].
+//!
+//! [`create_buf`]: crate::create_buf
+
+use std::cell::{Cell, RefCell};
+
+use crate::opts::CreateAutocmdOpts;
+use crate::types::{AutocmdCallbackArgs, LogLevel};
+use crate::{Buffer, Result};
+
+thread_local! {
+    static SCRATCH_BUFFERS: RefCell<Vec<Buffer>> =
+        const { RefCell::new(Vec::new()) };
+
+    static REPORT_ON_EXIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Records a scratch buffer created via [`create_buf`](crate::create_buf).
+///
+/// The first time a buffer is tracked this also registers a `VimLeavePre`
+/// autocommand calling [`report_leaked_scratch_buffers`], so that the leaks
+/// are reported when Neovim exits without the plugin having to do it.
+pub(crate) fn track(buf: &Buffer) {
+    SCRATCH_BUFFERS.with(|bufs| bufs.borrow_mut().push(buf.clone()));
+
+    if !REPORT_ON_EXIT.with(|registered| registered.replace(true)) {
+        let opts = CreateAutocmdOpts::builder()
+            .desc("Reports the scratch buffers leaked by nvim-oxi plugins")
+            .callback(|_: AutocmdCallbackArgs| {
+                self::report_leaked_scratch_buffers().map(|_| true)
+            })
+            .once(true)
+            .build();
+
+        // This is a debugging aid, so failing to register the autocommand
+        // shouldn't make `create_buf` fail.
+        if crate::create_autocmd(["VimLeavePre"], &opts).is_err() {
+            REPORT_ON_EXIT.with(|registered| registered.set(false));
+        }
+    }
+}
+
+/// Returns the unlisted scratch buffers created via
+/// [`create_buf`](crate::create_buf) that haven't been deleted yet. The
+/// buffers that have been deleted in the meantime are forgotten.
+pub fn leaked_scratch_buffers() -> Vec<Buffer> {
+    SCRATCH_BUFFERS.with(|bufs| {
+        let mut bufs = bufs.borrow_mut();
+        bufs.retain(Buffer::is_valid);
+        bufs.clone()
+    })
+}
+
+/// Warns about the scratch buffers returned by [`leaked_scratch_buffers`],
+/// if any, returning them. This is called automatically when Neovim exits,
+/// but plugins can also call it when they're unloaded.
+pub fn report_leaked_scratch_buffers() -> Result<Vec<Buffer>> {
+    let leaked = self::leaked_scratch_buffers();
+
+    if !leaked.is_empty() {
+        let handles = leaked
+            .iter()
+            .map(|buf| buf.0.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        crate::notify(
+            &format!(
+                "{} scratch buffer(s) were created but never deleted: {}",
+                leaked.len(),
+                handles
+            ),
+            LogLevel::Warn,
+            &Default::default(),
+        )?;
+    }
+
+    Ok(leaked)
+}
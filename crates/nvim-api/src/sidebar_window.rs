@@ -0,0 +1,80 @@
+use crate::types::SplitModifier;
+use crate::{Buffer, Result, Window};
+
+/// A window displaying a given buffer in a vertical split spanning the whole
+/// height of the editor, like the file trees or outlines of many plugins.
+///
+/// The window is created when the sidebar is opened and closed with
+/// [`hide`](Window::hide) when it's closed, so the buffer can be displayed
+/// again on the next [`open`](SidebarWindow::open).
+#[derive(Clone, Debug)]
+pub struct SidebarWindow {
+    buffer: Buffer,
+    position: SplitModifier,
+    width: u32,
+    window: Option<Window>,
+}
+
+impl SidebarWindow {
+    /// Creates a new closed sidebar displaying `buffer` in a window `width`
+    /// columns wide. The window will be opened according to `position`, e.g.
+    /// [`SplitModifier::TopLeft`] for a sidebar on the far left.
+    pub fn new(buffer: Buffer, position: SplitModifier, width: u32) -> Self {
+        Self { buffer, position, width, window: None }
+    }
+
+    /// Returns the window of the sidebar if it's currently open.
+    pub fn window(&self) -> Option<&Window> {
+        self.window.as_ref().filter(|win| win.is_valid())
+    }
+
+    /// Whether the sidebar is currently open. This also accounts for the
+    /// window having been closed by other means, e.g. by `:close`.
+    pub fn is_open(&self) -> bool {
+        self.window().is_some()
+    }
+
+    /// Opens the sidebar if it's not already open, without moving the cursor
+    /// to it.
+    pub fn open(&mut self) -> Result<()> {
+        if self.is_open() {
+            return Ok(());
+        }
+
+        let modifier = match self.position {
+            SplitModifier::AboveLeft => "aboveleft",
+            SplitModifier::BelowRight => "belowright",
+            SplitModifier::TopLeft => "topleft",
+            SplitModifier::BotRight => "botright",
+        };
+
+        let current = crate::get_current_win();
+        crate::command(&format!("{modifier} vertical split"))?;
+
+        let mut window = crate::get_current_win();
+        window.set_buf(&self.buffer)?;
+        window.set_width(self.width)?;
+        crate::set_current_win(&current)?;
+
+        self.window = Some(window);
+        Ok(())
+    }
+
+    /// Closes the sidebar if it's open.
+    pub fn close(&mut self) -> Result<()> {
+        match self.window.take() {
+            Some(window) if window.is_valid() => window.hide(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Opens the sidebar if it's closed or closes it if it's open. Returns
+    /// whether the sidebar is open after the call.
+    pub fn toggle(&mut self) -> Result<bool> {
+        if self.is_open() {
+            self.close().map(|()| false)
+        } else {
+            self.open().map(|()| true)
+        }
+    }
+}
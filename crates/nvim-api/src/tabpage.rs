@@ -7,6 +7,7 @@ use nvim_types::{
     Object,
     TabHandle,
     ToObject,
+    ToObjectResult,
 };
 use serde::{Deserialize, Serialize};
 
@@ -18,7 +19,11 @@ use crate::Result;
 /// A newtype struct wrapping a Neovim tabpage. All the `nvim_tabpage_*`
 /// functions taking a tabpage handle as their first argument are implemented
 /// as methods on this object.
+///
+/// Like [`Buffer`](crate::Buffer)s, tabpages (de)serialize to their integer
+/// handle, which is only valid within the current Neovim session.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct TabPage(pub(crate) TabHandle);
 
 impl fmt::Debug for TabPage {
@@ -45,6 +50,12 @@ impl From<TabPage> for Object {
     }
 }
 
+impl ToObject for TabPage {
+    fn to_obj(self) -> ToObjectResult {
+        Ok(self.0.into())
+    }
+}
+
 impl FromObject for TabPage {
     fn from_obj(obj: Object) -> FromObjectResult<Self> {
         Ok(TabHandle::from_obj(obj)?.into())
@@ -139,4 +150,16 @@ impl TabPage {
         };
         err.into_err_or_else(|| ())
     }
+
+    /// Binding to [`nvim_tabpage_set_win`](https://neovim.io/doc/user/api.html#nvim_tabpage_set_win()).
+    ///
+    /// Sets the current window in a tabpage. Fails if the window doesn't
+    /// belong to the tabpage.
+    #[cfg(feature = "neovim-nightly")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "neovim-nightly")))]
+    pub fn set_win(&mut self, win: &Window) -> Result<()> {
+        let mut err = nvim::Error::new();
+        unsafe { nvim_tabpage_set_win(self.0, win.0, &mut err) };
+        err.into_err_or_else(|| ())
+    }
 }
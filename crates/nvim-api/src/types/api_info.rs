@@ -0,0 +1,54 @@
+use nvim_types::{
+    Deserializer,
+    Dictionary,
+    FromObject,
+    FromObjectResult,
+    Object,
+};
+use serde::Deserialize;
+
+/// The API metadata of the running Neovim instance, as returned by
+/// [`api::get_api_info`](crate::get_api_info).
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct ApiInfo {
+    /// The version of Neovim and of its API.
+    pub version: ApiVersion,
+
+    /// The whole metadata dictionary, including the `functions`,
+    /// `ui_events`, `ui_options`, `error_types` and `types` keys. See `:h
+    /// api-metadata` for details.
+    pub metadata: Dictionary,
+}
+
+/// The `version` field of the API metadata.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+
+    /// The API level of this Neovim version.
+    pub api_level: u32,
+
+    /// The oldest API level this version is compatible with.
+    pub api_compatible: u32,
+
+    /// Whether the API of this version is still subject to change.
+    pub api_prerelease: bool,
+
+    /// Whether this is a prerelease (e.g. nightly) build of Neovim. Only
+    /// reported by newer versions, `false` otherwise.
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+impl FromObject for ApiInfo {
+    fn from_obj(obj: Object) -> FromObjectResult<Self> {
+        let metadata = Dictionary::from_obj(obj)?;
+        let version = metadata.get(&"version").cloned().unwrap_or_default();
+        let version = ApiVersion::deserialize(Deserializer::new(version))?;
+        Ok(Self { version, metadata })
+    }
+}
@@ -0,0 +1,42 @@
+use nvim_types::{Serializer, ToObject, ToObjectResult};
+use serde::{Deserialize, Serialize};
+
+/// Possible values of the `buftype` option. See `:h 'buftype'` for details.
+#[non_exhaustive]
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BufType {
+    /// A normal buffer.
+    #[default]
+    #[serde(rename = "")]
+    Normal,
+
+    /// A buffer which is not related to a file and will not be written.
+    Nofile,
+
+    /// A buffer which will not be written.
+    Nowrite,
+
+    /// A buffer which will always be written with `BufWriteCmd`s.
+    Acwrite,
+
+    /// The quickfix or location list buffer.
+    Quickfix,
+
+    /// A help buffer.
+    Help,
+
+    /// A terminal buffer.
+    Terminal,
+
+    /// A prompt buffer.
+    Prompt,
+}
+
+impl ToObject for BufType {
+    fn to_obj(self) -> ToObjectResult {
+        self.serialize(Serializer::new()).map_err(Into::into)
+    }
+}
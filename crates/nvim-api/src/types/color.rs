@@ -0,0 +1,102 @@
+use nvim_types::{self as nvim, Object};
+
+use crate::{Error, Result};
+
+/// A highlight color, passed to the `foreground`, `background` and `special`
+/// setters of [`SetHighlightOpts`](crate::opts::SetHighlightOpts).
+///
+/// Can be created from a `u32`, parsed from a `"#rrggbb"` hex string via
+/// [`Color::from_hex`], or converted from a string with [`TryFrom`], which
+/// fails if the string starts with a `#` but isn't a valid hex color. Any
+/// other string is passed to Neovim as a color name.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Color {
+    /// An RGB color, stored as a `0xRRGGBB` integer.
+    Rgb(u32),
+
+    /// A color name like `"Red"`, or one of the special values `"NONE"`,
+    /// `"bg"` and `"fg"`. See `:h gui-colors` for the valid names.
+    Named(String),
+}
+
+impl Color {
+    /// Parses a color from a hex string of the form `"#rrggbb"`, returning an
+    /// error if the string is malformed.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        hex.strip_prefix('#')
+            .filter(|digits| {
+                digits.len() == 6
+                    && digits.bytes().all(|b| b.is_ascii_hexdigit())
+            })
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .map(Self::Rgb)
+            .ok_or_else(|| Error::custom(format!("invalid hex color {hex:?}")))
+    }
+}
+
+impl From<u32> for Color {
+    #[inline]
+    fn from(rgb: u32) -> Self {
+        Self::Rgb(rgb)
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = Error;
+
+    /// Strings starting with a `#` are parsed with [`Color::from_hex`], any
+    /// other string becomes a [`Color::Named`].
+    fn try_from(color: &str) -> Result<Self> {
+        if color.starts_with('#') {
+            Self::from_hex(color)
+        } else {
+            Ok(Self::Named(color.to_owned()))
+        }
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(color: String) -> Result<Self> {
+        Self::try_from(color.as_str())
+    }
+}
+
+impl From<Color> for Object {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Rgb(rgb) => rgb.into(),
+            Color::Named(name) => nvim::String::from(name).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex() {
+        assert_eq!(Ok(Color::Rgb(0xff8800)), Color::from_hex("#ff8800"));
+        assert_eq!(Ok(Color::Rgb(0xABCDEF)), Color::from_hex("#ABCDEF"));
+    }
+
+    #[test]
+    fn try_from_str() {
+        assert_eq!(Ok(Color::Rgb(0xff8800)), Color::try_from("#ff8800"));
+        assert_eq!(Ok(Color::Named("Red".into())), Color::try_from("Red"));
+        assert_eq!(Ok(Color::Named("NONE".into())), Color::try_from("NONE"));
+        assert!(Color::try_from("#ff88").is_err());
+    }
+
+    #[test]
+    fn from_hex_invalid() {
+        assert!(Color::from_hex("ff8800").is_err());
+        assert!(Color::from_hex("#ff880").is_err());
+        assert!(Color::from_hex("#+f8800").is_err());
+        assert!(Color::from_hex("#gg8800").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::panic::{self, AssertUnwindSafe};
+
 use nvim_types::{Function, Serializer, ToObject, ToObjectResult};
 use serde::Serialize;
 
@@ -51,3 +53,28 @@ impl ToObject for CommandComplete {
         self.serialize(Serializer::new()).map_err(Into::into)
     }
 }
+
+/// Creates a [`CommandComplete::CustomList`] from a closure taking the
+/// leading portion of the argument being completed, the whole command line
+/// and the cursor position in it, and returning the completion candidates.
+///
+/// If the closure panics no candidates are returned.
+impl<F> From<F> for CommandComplete
+where
+    F: Fn(&str, &str, usize) -> crate::Result<Vec<String>> + 'static,
+{
+    fn from(fun: F) -> Self {
+        let fun = move |(arg_lead, cmd_line, cursor_pos): (
+            String,
+            String,
+            usize,
+        )| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                fun(&arg_lead, &cmd_line, cursor_pos)
+            }))
+            .unwrap_or_else(|_| Ok(Vec::new()))
+        };
+
+        Self::CustomList(Function::from_fn(fun))
+    }
+}
@@ -0,0 +1,49 @@
+use nvim_types::{Dictionary, Object};
+
+/// A candidate shown in the insert-mode completion popup by
+/// [`api::complete`](crate::complete). See `:h complete-items` for details.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CompleteItem {
+    /// The text that will be inserted.
+    pub word: String,
+
+    /// An abbreviation of `word` shown in the menu instead of it.
+    pub abbr: Option<String>,
+
+    /// Extra text shown in the menu after `word` or `abbr`.
+    pub menu: Option<String>,
+
+    /// More information about the item, shown in the preview window.
+    pub info: Option<String>,
+
+    /// A single letter indicating the type of the completion.
+    pub kind: Option<String>,
+
+    /// Whether to ignore case when comparing the item with other items.
+    pub icase: bool,
+
+    /// Whether to add the item even if an item with the same `word` is
+    /// already present.
+    pub dup: bool,
+}
+
+impl<S: Into<String>> From<S> for CompleteItem {
+    fn from(word: S) -> Self {
+        Self { word: word.into(), ..Default::default() }
+    }
+}
+
+impl From<CompleteItem> for Object {
+    fn from(item: CompleteItem) -> Self {
+        Dictionary::from_iter([
+            ("word", Object::from(item.word)),
+            ("abbr", item.abbr.into()),
+            ("menu", item.menu.into()),
+            ("info", item.info.into()),
+            ("kind", item.kind.into()),
+            ("icase", (item.icase as i64).into()),
+            ("dup", (item.dup as i64).into()),
+        ])
+        .into()
+    }
+}
@@ -0,0 +1,24 @@
+/// The position of the cursor in a window, as returned by
+/// [`Window::get_cursor`](crate::Window::get_cursor).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CursorPosition {
+    /// The line the cursor is on (1-indexed).
+    pub row: usize,
+
+    /// The byte offset of the cursor in its line (0-indexed).
+    pub col: usize,
+}
+
+impl From<(usize, usize)> for CursorPosition {
+    #[inline]
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<CursorPosition> for (usize, usize) {
+    #[inline]
+    fn from(pos: CursorPosition) -> Self {
+        (pos.row, pos.col)
+    }
+}
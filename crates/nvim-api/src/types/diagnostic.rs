@@ -0,0 +1,44 @@
+use nvim_types::{Deserializer, FromObject, FromObjectResult, Object};
+use serde::Deserialize;
+use serde_repr::Deserialize_repr;
+
+/// A diagnostic reported through `vim.diagnostic`, as returned by
+/// [`Buffer::diagnostics`](crate::Buffer::diagnostics). See `:h
+/// diagnostic-structure` for details.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct Diagnostic {
+    /// The line the diagnostic starts on (0-indexed).
+    #[serde(rename = "lnum")]
+    pub line: usize,
+
+    /// The column the diagnostic starts at (0-indexed).
+    pub col: usize,
+
+    /// The severity of the diagnostic.
+    pub severity: DiagnosticSeverity,
+
+    /// The diagnostic text.
+    pub message: String,
+
+    /// The source of the diagnostic, e.g. the name of a language server.
+    pub source: Option<String>,
+}
+
+/// The severity of a [`Diagnostic`], matching `vim.diagnostic.severity`.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize_repr,
+)]
+#[repr(u8)]
+pub enum DiagnosticSeverity {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Hint = 4,
+}
+
+impl FromObject for Diagnostic {
+    fn from_obj(obj: Object) -> FromObjectResult<Self> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
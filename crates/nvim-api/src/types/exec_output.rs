@@ -0,0 +1,19 @@
+use crate::Error;
+
+/// The result of running Ex commands with
+/// [`api::exec_output`](crate::exec_output).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecOutput {
+    /// The captured output, empty if the commands didn't print anything.
+    /// Error messages are not part of it.
+    pub output: String,
+
+    /// The error that aborted the commands, if any.
+    pub error: Option<Error>,
+
+    /// Whether any error or warning message was emitted while running the
+    /// commands, even if it didn't abort them (e.g. because of `:silent!` or
+    /// `:try`).
+    pub had_error: bool,
+}
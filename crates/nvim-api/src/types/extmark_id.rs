@@ -0,0 +1,47 @@
+use std::fmt;
+
+use nvim_types::{Integer, Object};
+use serde::Deserialize;
+
+/// The id of an extmark, as returned by
+/// [`Buffer::set_extmark`](crate::Buffer::set_extmark). Kept distinct from
+/// plain integers so that it can't be confused with a namespace id.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize,
+)]
+#[serde(transparent)]
+pub struct ExtmarkId(pub u32);
+
+impl From<u32> for ExtmarkId {
+    #[inline]
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<ExtmarkId> for u32 {
+    #[inline]
+    fn from(id: ExtmarkId) -> Self {
+        id.0
+    }
+}
+
+impl From<ExtmarkId> for Integer {
+    #[inline]
+    fn from(id: ExtmarkId) -> Self {
+        id.0.into()
+    }
+}
+
+impl From<ExtmarkId> for Object {
+    #[inline]
+    fn from(id: ExtmarkId) -> Self {
+        id.0.into()
+    }
+}
+
+impl fmt::Display for ExtmarkId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
@@ -1,6 +1,8 @@
 use nvim_types::{Array, Integer, Object};
 use serde::Deserialize;
 
+use super::ExtmarkId;
+
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum ExtmarkPosition {
@@ -9,7 +11,10 @@ pub enum ExtmarkPosition {
     ByTuple((usize, usize)),
 
     /// Defines the extmark's position in the buffer by its id.
-    ById(u32),
+    ById(ExtmarkId),
+
+    /// The end of the buffer, i.e. the position after its last character.
+    End,
 }
 
 impl From<ExtmarkPosition> for Object {
@@ -21,6 +26,9 @@ impl From<ExtmarkPosition> for Object {
                 Array::from_iter([row as Integer, col as Integer]).into()
             },
             ById(extmark_id) => extmark_id.into(),
+            // Neovim clamps negative rows and columns to the end of the
+            // buffer.
+            End => Array::from_iter([-1 as Integer, -1]).into(),
         }
     }
 }
@@ -0,0 +1,24 @@
+/// Whether the keys passed to
+/// [`api::feedkeys_and_wait`](crate::feedkeys_and_wait) are remapped. See
+/// `:h feedkeys()` for more infos.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum FeedkeysMode {
+    /// Keys are remapped (the `m` flag).
+    #[default]
+    Remap,
+
+    /// Keys are not remapped (the `n` flag).
+    NoRemap,
+}
+
+impl FeedkeysMode {
+    /// Returns the flags passed to `nvim_feedkeys` to execute the keys
+    /// synchronously.
+    pub(crate) const fn execute_flags(&self) -> &'static str {
+        match self {
+            Self::Remap => "mx",
+            Self::NoRemap => "nx",
+        }
+    }
+}
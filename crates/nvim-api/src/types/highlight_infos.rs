@@ -28,3 +28,51 @@ impl FromObject for HighlightInfos {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
     }
 }
+
+macro_rules! highlight_fields {
+    ($($variant:ident => $field:ident,)*) => {
+        /// A field of [`HighlightInfos`], returned by [`HighlightInfos::diff`].
+        #[non_exhaustive]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        pub enum HighlightField {
+            $(
+                #[doc = concat!("The `", stringify!($field), "` field.")]
+                $variant,
+            )*
+        }
+
+        impl HighlightInfos {
+            /// Returns the fields whose value differs between `self` and
+            /// `other`, in the order they're declared in. A field that's set
+            /// in only one of the two also counts as changed.
+            pub fn diff(&self, other: &HighlightInfos) -> Vec<HighlightField> {
+                let mut fields = Vec::new();
+                $(
+                    if self.$field != other.$field {
+                        fields.push(HighlightField::$variant);
+                    }
+                )*
+                fields
+            }
+        }
+    };
+}
+
+highlight_fields! {
+    Background => background,
+    BgIndexed => bg_indexed,
+    Blend => blend,
+    Bold => bold,
+    FgIndexed => fg_indexed,
+    Foreground => foreground,
+    Italic => italic,
+    Reverse => reverse,
+    Special => special,
+    Standout => standout,
+    Strikethrough => strikethrough,
+    Undercurl => undercurl,
+    Underdash => underdash,
+    Underdot => underdot,
+    Underline => underline,
+    Underlineline => underlineline,
+}
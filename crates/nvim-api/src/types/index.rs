@@ -0,0 +1,42 @@
+use std::num::TryFromIntError;
+
+use nvim_types::Integer;
+
+/// A 0-indexed line index which can be counted either from the start or from
+/// the end of a buffer. Used by [`Buffer::get_lines`](crate::Buffer::get_lines)
+/// and [`Buffer::set_lines`](crate::Buffer::set_lines) to express Neovim's
+/// negative indices.
+///
+/// Plain `usize`s are converted into [`Index::FromStart`].
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Index {
+    /// The `n`-th line counting from the start of the buffer.
+    FromStart(usize),
+
+    /// The `n`-th line counting backwards from the end of the buffer.
+    /// `FromEnd(0)` refers to the index one past the last line, which makes it
+    /// useful as an end-exclusive upper bound, while `FromEnd(1)` refers to
+    /// the last line.
+    FromEnd(usize),
+}
+
+impl From<usize> for Index {
+    #[inline]
+    fn from(n: usize) -> Self {
+        Self::FromStart(n)
+    }
+}
+
+impl TryFrom<Index> for Integer {
+    type Error = TryFromIntError;
+
+    #[inline]
+    fn try_from(index: Index) -> Result<Self, Self::Error> {
+        match index {
+            Index::FromStart(n) => n.try_into(),
+            // Neovim interprets negative indices as `length + 1 + index`.
+            Index::FromEnd(n) => Integer::try_from(n).map(|n| -1 - n),
+        }
+    }
+}
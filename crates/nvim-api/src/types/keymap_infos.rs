@@ -3,12 +3,14 @@ use nvim_types::{
     FromObject,
     FromObjectResult,
     Function,
+    LuaFunction,
     Object,
 };
-use serde::Deserialize;
+use serde::{de, Deserialize};
 
 use super::Mode;
 use crate::serde_utils as utils;
+use crate::{Error, Result};
 
 #[non_exhaustive]
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize)]
@@ -18,7 +20,8 @@ pub struct KeymapInfos {
     pub buffer: bool,
 
     /// Optional callback triggered by the keymap.
-    pub callback: Option<Function<(), ()>>,
+    #[serde(default, deserialize_with = "owned_callback")]
+    pub callback: Option<LuaFunction>,
 
     /// Whether the keymap argument is an expression.
     #[serde(deserialize_with = "utils::bool_from_int")]
@@ -60,6 +63,66 @@ pub struct KeymapInfos {
     pub silent: bool,
 }
 
+impl KeymapInfos {
+    /// Whether the right-hand side of the mapping is a Lua callback.
+    #[inline]
+    pub fn has_callback(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// Invokes the Lua callback of the mapping, returning an error if the
+    /// mapping doesn't have one.
+    ///
+    /// Invoking a mapping that was deleted or redefined with a different
+    /// callback after it was queried also returns an error. Buffer-local
+    /// mappings are looked up in the current buffer.
+    pub fn invoke(&self) -> Result<()> {
+        let callback = self.callback.as_ref().ok_or_else(|| {
+            Error::custom(format!("Mapping {} has no callback", self.lhs))
+        })?;
+
+        let is_mapped = |keymap: KeymapInfos| {
+            keymap.lhs == self.lhs
+                && keymap.callback.as_ref() == Some(callback)
+        };
+
+        let still_mapped = if self.buffer {
+            crate::Buffer::current().get_keymap(self.mode)?.any(is_mapped)
+        } else {
+            crate::get_keymap(self.mode).any(is_mapped)
+        };
+
+        if !still_mapped {
+            return Err(Error::custom(format!(
+                "Mapping {} was deleted or redefined",
+                self.lhs
+            )));
+        }
+
+        callback.call(()).map_err(Into::into)
+    }
+}
+
+/// `nvim_get_keymap` returns a new registry reference to the callback, which
+/// is owned by the caller. We take a `LuaFunction` to the same function,
+/// which is released when dropped, and remove the original reference.
+fn owned_callback<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<LuaFunction>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let callback = match Option::<Function<(), ()>>::deserialize(deserializer)?
+    {
+        Some(callback) => callback,
+        None => return Ok(None),
+    };
+    let owned = LuaFunction::from_obj(Object::from(callback.clone()))
+        .map_err(de::Error::custom)?;
+    callback.remove_from_lua_registry();
+    Ok(Some(owned))
+}
+
 impl FromObject for KeymapInfos {
     fn from_obj(obj: Object) -> FromObjectResult<Self> {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
@@ -1,10 +1,13 @@
+mod api_info;
 mod autocmd_callback_args;
 mod autocmd_infos;
+mod buf_type;
 mod channel_infos;
 mod client_infos;
 mod cmd_infos;
 mod cmd_magic;
 mod cmd_range;
+mod color;
 mod command_addr;
 mod command_args;
 mod command_complete;
@@ -12,19 +15,28 @@ mod command_infos;
 mod command_modifiers;
 mod command_nargs;
 mod command_range;
+mod complete_item;
 mod context_type;
+mod cursor_position;
+mod diagnostic;
 mod editor_context;
+mod exec_output;
 mod extmark_hl_mode;
+mod extmark_id;
 mod extmark_infos;
 mod extmark_position;
 mod extmark_virt_text_position;
+mod feedkeys_mode;
 mod got_mode;
 mod highlight_infos;
+mod index;
 mod keymap_infos;
 mod log_level;
 mod mode;
+mod mode_change_args;
 mod mouse_action;
 mod mouse_button;
+mod namespace;
 mod option_infos;
 mod parsed_viml_expression;
 mod paste_phase;
@@ -33,22 +45,29 @@ mod register_type;
 mod split_modifier;
 mod statusline_highlight_infos;
 mod statusline_infos;
+mod text_edit;
 mod ui_infos;
 mod viml_ast_node;
+mod win_position;
+mod win_view;
 mod window_anchor;
 mod window_border;
 mod window_border_char;
 mod window_config;
+mod window_option;
 mod window_relative_to;
 mod window_style;
 
+pub use api_info::*;
 pub use autocmd_callback_args::*;
 pub use autocmd_infos::*;
+pub use buf_type::*;
 pub use channel_infos::*;
 pub use client_infos::*;
 pub use cmd_infos::*;
 pub use cmd_magic::*;
 pub use cmd_range::*;
+pub use color::*;
 pub use command_addr::*;
 pub use command_args::*;
 pub use command_complete::*;
@@ -56,19 +75,28 @@ pub use command_infos::*;
 pub use command_modifiers::*;
 pub use command_nargs::*;
 pub use command_range::*;
+pub use complete_item::*;
 pub use context_type::*;
+pub use cursor_position::*;
+pub use diagnostic::*;
 pub use editor_context::*;
+pub use exec_output::*;
 pub use extmark_hl_mode::*;
+pub use extmark_id::*;
 pub use extmark_infos::*;
 pub use extmark_position::*;
 pub use extmark_virt_text_position::*;
+pub use feedkeys_mode::*;
 pub use got_mode::*;
 pub use highlight_infos::*;
+pub use index::*;
 pub use keymap_infos::*;
 pub use log_level::*;
 pub use mode::*;
+pub use mode_change_args::*;
 pub use mouse_action::*;
 pub use mouse_button::*;
+pub use namespace::*;
 pub use option_infos::*;
 pub use parsed_viml_expression::*;
 pub use paste_phase::*;
@@ -77,11 +105,15 @@ pub use register_type::*;
 pub use split_modifier::*;
 pub use statusline_highlight_infos::*;
 pub use statusline_infos::*;
+pub use text_edit::*;
 pub use ui_infos::*;
 pub use viml_ast_node::*;
+pub use win_position::*;
+pub use win_view::*;
 pub use window_anchor::*;
 pub use window_border::*;
 pub use window_border_char::*;
 pub use window_config::*;
+pub use window_option::*;
 pub use window_relative_to::*;
 pub use window_style::*;
@@ -48,6 +48,26 @@ macro_rules! is_mode {
 }
 
 impl Mode {
+    /// Parses a mode as returned by `mode(1)`, e.g. in the `<amatch>` of a
+    /// `ModeChanged` autocommand. Returns `None` for the modes that don't
+    /// have a corresponding variant, like the hit-enter prompt.
+    pub(crate) fn from_full_mode(mode: &str) -> Option<Self> {
+        let mut chars = mode.chars();
+
+        let mode = match chars.next()? {
+            'n' if chars.next() == Some('o') => Mode::OperatorPending,
+            'n' => Mode::Normal,
+            'v' | 'V' | '\x16' => Mode::Visual,
+            's' | 'S' | '\x13' => Mode::Select,
+            'i' | 'R' => Mode::Insert,
+            'c' => Mode::CmdLine,
+            't' => Mode::Terminal,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+
     is_mode!(is_cmd_line, CmdLine);
     is_mode!(is_insert, Insert);
     is_mode!(is_langmap, Langmap);
@@ -0,0 +1,54 @@
+use super::Mode;
+
+/// Arguments passed to the callback registered with
+/// [`api::on_mode_change`](crate::on_mode_change).
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ModeChangeArgs {
+    /// The mode that was left.
+    pub old: Mode,
+
+    /// The mode that was entered.
+    pub new: Mode,
+
+    /// The raw `old:new` match string of the `ModeChanged` event, with the
+    /// modes as returned by `mode(1)`, e.g. `"n:no"`.
+    pub r#match: String,
+}
+
+impl ModeChangeArgs {
+    /// Parses the `<amatch>` of a `ModeChanged` autocommand.
+    pub(crate) fn from_match(r#match: String) -> Option<Self> {
+        let (old, new) = r#match.split_once(':')?;
+        let old = Mode::from_full_mode(old)?;
+        let new = Mode::from_full_mode(new)?;
+        Some(Self { old, new, r#match })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_match() {
+        let args = ModeChangeArgs::from_match("n:i".into()).unwrap();
+        assert_eq!((Mode::Normal, Mode::Insert), (args.old, args.new));
+
+        let args = ModeChangeArgs::from_match("niI:no\x16".into()).unwrap();
+        assert_eq!(
+            (Mode::Normal, Mode::OperatorPending),
+            (args.old, args.new)
+        );
+
+        let args = ModeChangeArgs::from_match("\x16s:Rv".into()).unwrap();
+        assert_eq!((Mode::Visual, Mode::Insert), (args.old, args.new));
+    }
+
+    #[test]
+    fn from_match_invalid() {
+        assert_eq!(None, ModeChangeArgs::from_match("n".into()));
+        assert_eq!(None, ModeChangeArgs::from_match("n:r".into()));
+        assert_eq!(None, ModeChangeArgs::from_match(":i".into()));
+    }
+}
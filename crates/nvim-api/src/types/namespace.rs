@@ -0,0 +1,47 @@
+use std::fmt;
+
+use nvim_types::{Integer, Object};
+use serde::Deserialize;
+
+/// The id of a namespace, as returned by
+/// [`create_namespace`](crate::create_namespace). Kept distinct from plain
+/// integers so that it can't be confused with an extmark or highlight id.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Namespace(pub u32);
+
+impl From<u32> for Namespace {
+    #[inline]
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<Namespace> for u32 {
+    #[inline]
+    fn from(id: Namespace) -> Self {
+        id.0
+    }
+}
+
+impl From<Namespace> for Integer {
+    #[inline]
+    fn from(id: Namespace) -> Self {
+        id.0.into()
+    }
+}
+
+impl From<Namespace> for Object {
+    #[inline]
+    fn from(id: Namespace) -> Self {
+        id.0.into()
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
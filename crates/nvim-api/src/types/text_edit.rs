@@ -0,0 +1,15 @@
+/// A textual edit applied to a buffer by
+/// [`Buffer::apply_edits`](crate::Buffer::apply_edits), modelled after the
+/// LSP `TextEdit`. Positions are zero-based `(row, col)` tuples, with the
+/// column counted in bytes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TextEdit {
+    /// The start of the range to replace.
+    pub start: (usize, usize),
+
+    /// The end of the range to replace (exclusive).
+    pub end: (usize, usize),
+
+    /// The replacement text. Newlines split it into multiple lines.
+    pub new_text: String,
+}
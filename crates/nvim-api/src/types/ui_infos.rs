@@ -29,7 +29,7 @@ pub struct UiInfos {
     /// `true` if the UI uses RGB colors.
     pub rgb: bool,
 
-    /// Requested height of the UI.
+    /// Requested width of the UI.
     pub width: usize,
 }
 
@@ -0,0 +1,25 @@
+/// The position of a window in display cells, as returned by
+/// [`Window::get_position`](crate::Window::get_position). Both coordinates
+/// are 0-indexed and refer to the window's top-left corner.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct WinPosition {
+    /// The screen row.
+    pub row: usize,
+
+    /// The screen column.
+    pub col: usize,
+}
+
+impl From<(usize, usize)> for WinPosition {
+    #[inline]
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<WinPosition> for (usize, usize) {
+    #[inline]
+    fn from(pos: WinPosition) -> Self {
+        (pos.row, pos.col)
+    }
+}
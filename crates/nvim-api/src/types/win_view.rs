@@ -0,0 +1,55 @@
+use nvim_types::{
+    Deserializer,
+    FromObject,
+    FromObjectResult,
+    Object,
+    Serializer,
+    ToObject,
+    ToObjectResult,
+};
+use serde::{Deserialize, Serialize};
+
+/// The view of a window as returned by
+/// [`Window::save_view`](crate::Window::save_view). See `:h winsaveview()`
+/// for more infos.
+#[non_exhaustive]
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize, Serialize,
+)]
+pub struct WinView {
+    /// Cursor line number.
+    pub lnum: u32,
+
+    /// Cursor column (0-indexed).
+    pub col: u32,
+
+    /// Cursor column offset for `virtualedit`.
+    pub coladd: u32,
+
+    /// Preferred column for vertical movement.
+    pub curswant: u32,
+
+    /// First line in the window.
+    pub topline: u32,
+
+    /// Filler lines, only in diff mode.
+    pub topfill: u32,
+
+    /// First column displayed. Only used when `wrap` is off.
+    pub leftcol: u32,
+
+    /// First column displayed. Only used when `wrap` is on.
+    pub skipcol: u32,
+}
+
+impl FromObject for WinView {
+    fn from_obj(obj: Object) -> FromObjectResult<Self> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+impl ToObject for WinView {
+    fn to_obj(self) -> ToObjectResult {
+        self.serialize(Serializer::new()).map_err(Into::into)
+    }
+}
@@ -11,6 +11,7 @@ use nvim_types::{
 use serde::Deserialize;
 
 use super::{WindowAnchor, WindowBorder, WindowRelativeTo, WindowStyle};
+use crate::Window;
 
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, PartialEq, Builder, Deserialize)]
@@ -42,7 +43,7 @@ pub struct WindowConfig {
     pub external: Option<bool>,
 
     /// Enable focus by user actions like mouse events. Non-focusable windows
-    /// can be entered by `crate::api::set_current_win`.
+    /// can be entered by [`api::set_current_win`](crate::set_current_win).
     #[builder(setter(strip_option))]
     pub focusable: Option<bool>,
 
@@ -72,6 +73,18 @@ pub struct WindowConfig {
     #[builder(setter(strip_option))]
     pub width: Option<u32>,
 
+    /// The window the float is positioned relative to, when
+    /// [`relative`](WindowConfigBuilder::relative) is set to
+    /// [`WindowRelativeTo::Window`]. Takes precedence over the window stored
+    /// in `relative` if both are set. A handle of `0` stands for the window
+    /// that's current when the config is applied by
+    /// [`open_win`](crate::open_win) or
+    /// [`Window::set_config`](crate::Window::set_config), and
+    /// [`Window::get_config`](crate::Window::get_config) always returns the
+    /// actual window.
+    #[builder(setter(into, strip_option))]
+    pub win: Option<Window>,
+
     /// Stacking order. Windows with higher `zindex` go in front of windows
     /// with lower indices.
     #[builder(setter(strip_option))]
@@ -130,8 +143,10 @@ pub(crate) struct KeyDict_float_config {
 
 impl From<&WindowConfig> for KeyDict_float_config {
     fn from(config: &WindowConfig) -> Self {
-        let win = match &config.relative {
-            Some(WindowRelativeTo::Window(win)) => win.0.into(),
+        let win = match (&config.win, &config.relative) {
+            (Some(win), _) | (_, Some(WindowRelativeTo::Window(win))) => {
+                win.0.into()
+            },
             _ => Object::nil(),
         };
 
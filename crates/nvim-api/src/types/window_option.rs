@@ -0,0 +1,54 @@
+use nvim_types::{FromObject, Integer, ToObject};
+
+/// A window-local option whose value has a known Rust type. Used by
+/// [`Window::get_opt`](crate::Window::get_opt) and
+/// [`Window::set_opt`](crate::Window::set_opt) to catch typos in option
+/// names at compile time.
+///
+/// The implementors of this trait live in the [`window_options`] module.
+pub trait WindowOption {
+    /// The name of the option, e.g. `"number"`.
+    const NAME: &'static str;
+
+    /// The type of the option's value.
+    type Value: FromObject + ToObject;
+}
+
+macro_rules! window_options {
+    ($($opt:ident($name:literal, $value:ty);)*) => {
+        /// Typed window-local options implementing [`WindowOption`].
+        pub mod window_options {
+            use super::*;
+
+            $(
+                #[doc = concat!(
+                    "The `", $name, "` option. See `:h '", $name, "'` for details."
+                )]
+                #[derive(Copy, Clone, Debug)]
+                pub struct $opt;
+
+                impl WindowOption for $opt {
+                    const NAME: &'static str = $name;
+                    type Value = $value;
+                }
+            )*
+        }
+    };
+}
+
+window_options! {
+    ColorColumn("colorcolumn", String);
+    ConcealLevel("conceallevel", Integer);
+    CursorColumn("cursorcolumn", bool);
+    CursorLine("cursorline", bool);
+    FoldEnable("foldenable", bool);
+    FoldMethod("foldmethod", String);
+    List("list", bool);
+    Number("number", bool);
+    RelativeNumber("relativenumber", bool);
+    SignColumn("signcolumn", String);
+    Spell("spell", bool);
+    Winblend("winblend", Integer);
+    Winhighlight("winhighlight", String);
+    Wrap("wrap", bool);
+}
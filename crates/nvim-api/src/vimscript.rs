@@ -1,16 +1,30 @@
-use nvim_types::{self as nvim, Array, FromObject, Object};
+use luajit_bindings::{self as lua, macros::cstr, Poppable};
+use nvim_types::{
+    self as nvim,
+    Array,
+    Dictionary,
+    FromObject,
+    Function,
+    Integer,
+    LuaFunction,
+    LuaTable,
+    Object,
+};
 
 use super::ffi::vimscript::*;
 use super::types::*;
 use super::LUA_INTERNAL_CALL;
-use crate::Result;
+use crate::global::VvarsGuard;
+use crate::{Error, Result};
 
 /// Binding to [`nvim_call_dict_function`](https://neovim.io/doc/user/api.html#nvim_call_dict_function()).
 ///
 /// Calls a VimL dictionary function with the given arguments, returning the
-/// result of the funtion call.
+/// result of the funtion call. The dictionary can either be a
+/// [`Dictionary`](nvim::Dictionary) or a string evaluating to one (e.g.
+/// `"g:my_dict".into()`), and it's bound to `self` in the function.
 pub fn call_dict_function<Args, Ret>(
-    dict: &str,
+    dict: Object,
     func: &str,
     args: Args,
 ) -> Result<Ret>
@@ -18,7 +32,6 @@ where
     Args: Into<Array>,
     Ret: FromObject,
 {
-    let dict = Object::from(nvim::String::from(dict));
     let func = nvim::String::from(func);
     let args = args.into();
     let mut err = nvim::Error::new();
@@ -87,6 +100,63 @@ pub fn command(command: &str) -> Result<()> {
     err.into_err_or_else(|| ())
 }
 
+/// Calls the Vimscript [`complete()`](https://neovim.io/doc/user/builtin.html#complete())
+/// function, showing `items` in the insert-mode completion popup. The
+/// completed text starts at the 0-indexed byte column `col` of the current
+/// line.
+///
+/// If [`append`](super::opts::CompleteOptsBuilder::append) is set the items
+/// are instead added to the current matches with `complete_add()`, and an
+/// error is returned if one of them couldn't be added.
+///
+/// Like the Vimscript functions this can only be used in Insert mode, e.g.
+/// from an `<Cmd>` mapping or an `InsertCharPre` autocommand.
+pub fn complete(
+    col: usize,
+    items: Vec<CompleteItem>,
+    opts: &super::opts::CompleteOpts,
+) -> Result<()> {
+    if opts.append {
+        for item in items {
+            let word = item.word.clone();
+            // `complete_add()` returns 0 if the item couldn't be added, 1 if
+            // it was added and 2 if it was already in the list.
+            if self::call_function::<_, Integer>("complete_add", (item,))? == 0
+            {
+                return Err(Error::custom(format!(
+                    "couldn't add completion item {word:?}"
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    let col = Integer::try_from(col)? + 1;
+    let items = items.into_iter().collect::<Array>();
+    self::call_function::<_, Integer>("complete", (col, items))?;
+    Ok(())
+}
+
+/// Calls the Vimscript [`confirm()`](https://neovim.io/doc/user/builtin.html#confirm())
+/// function, asking the user to pick one of the `choices` after showing
+/// `msg`. Returns the 0-indexed position of the picked choice, or `None` if
+/// the dialog was cancelled with `<Esc>` or `<C-c>`. Pressing `<CR>` picks
+/// the choice at the `default` position.
+///
+/// An `&` in a choice marks the character after it as the shortcut key for
+/// that choice, see `:h confirm()` for more infos.
+pub fn confirm(
+    msg: &str,
+    choices: &[&str],
+    default: usize,
+) -> Result<Option<usize>> {
+    let choices = choices.join("\n");
+    let default = Integer::try_from(default)? + 1;
+    let picked =
+        self::call_function::<_, usize>("confirm", (msg, choices, default))?;
+    Ok(picked.checked_sub(1))
+}
+
 /// Binding to [`nvim_eval`](https://neovim.io/doc/user/api.html#nvim_eval()).
 ///
 /// Evaluates a VimL expression.
@@ -118,6 +188,145 @@ pub fn exec(src: &str, output: bool) -> Result<Option<String>> {
     })
 }
 
+/// Like [`exec`] with `output` set to `true`, but the output printed before
+/// an error is kept.
+///
+/// The output is captured with `:redir`, so the error that aborted the
+/// commands, if any, is returned in [`ExecOutput::error`] separately from
+/// it. [`ExecOutput::had_error`] also reports error and warning messages that
+/// didn't abort the commands (e.g. because of `:silent!`). `v:errmsg` and
+/// `v:warningmsg` are used to detect those, and they get their previous
+/// values back before this returns.
+pub fn exec_output(src: &str) -> Result<ExecOutput> {
+    const OUTPUT_VAR: &str = "__oxi_exec_output";
+
+    let _vvars = VvarsGuard::clear(&["errmsg", "warningmsg"])?;
+
+    self::command(&format!("redir => g:{OUTPUT_VAR}"))?;
+    let error = self::exec(src, false).err();
+    self::command("redir END")?;
+
+    let mut output = crate::get_var::<String>(OUTPUT_VAR)?;
+    crate::del_var(OUTPUT_VAR)?;
+
+    // Like `nvim_exec`, drop the newline printed before the first message.
+    if output.starts_with('\n') {
+        output.remove(0);
+    }
+
+    let had_error = error.is_some()
+        || !crate::get_vvar::<String>("errmsg")?.is_empty()
+        || !crate::get_vvar::<String>("warningmsg")?.is_empty();
+
+    Ok(ExecOutput { output, error, had_error })
+}
+
+/// Gets the API metadata of the running Neovim instance by calling the
+/// Vimscript [`api_info()`](https://neovim.io/doc/user/builtin.html#api_info())
+/// function. Useful to check the Neovim version at runtime.
+///
+/// Unlike [`nvim_get_api_info`](https://neovim.io/doc/user/api.html#nvim_get_api_info()),
+/// which can only be called over RPC, this doesn't return a channel id since
+/// plugins run inside the Neovim process.
+pub fn get_api_info() -> Result<ApiInfo> {
+    self::call_function("api_info", Array::new())
+}
+
+/// Calls the Vimscript [`has()`](https://neovim.io/doc/user/builtin.html#has())
+/// function, returning whether the running Neovim supports `feature`. Useful
+/// to check for version-gated APIs at runtime, e.g. with `has("nvim-0.9")`.
+pub fn has(feature: &str) -> bool {
+    matches!(self::call_function::<_, Integer>("has", (feature,)), Ok(1))
+}
+
+/// Calls the Vimscript [`input()`](https://neovim.io/doc/user/builtin.html#input())
+/// function, asking the user to type some text after showing `prompt`. The
+/// command line is pre-filled with `default`. Returns `None` if the prompt
+/// was cancelled with `<Esc>` or `<C-c>`, which unlike confirming an empty
+/// command line doesn't return an empty string.
+pub fn input_prompt(prompt: &str, default: &str) -> Result<Option<String>> {
+    let opts = Dictionary::from_iter([
+        ("prompt", Object::from(prompt)),
+        ("default", Object::from(default)),
+        ("cancelreturn", Object::nil()),
+    ]);
+    self::call_function("input", (opts,))
+}
+
+/// Binding to Lua's `require`.
+///
+/// Loads the Lua module with the given name, returning the table it
+/// evaluates to. If the module can't be found the error contains Lua's
+/// message.
+///
+/// The table is kept in the Lua registry instead of being converted to an
+/// [`Object`], so it can be used with any module, including the ones with
+/// functions and userdata like `vim`. Use [`lua_require`] to get a module
+/// whose value is plain data, and [`lua_require_function`] for one whose
+/// value is a function.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{api, LuaFunction};
+///
+/// let vim = api::require("vim")?;
+/// let inspect = vim.get::<_, LuaFunction>("inspect")?;
+/// let repr = inspect.call::<_, String>(42)?;
+/// ```
+pub fn require(module: &str) -> Result<LuaTable> {
+    self::lua_require_value(module)
+}
+
+/// Loads the Lua module with the given name, returning its value. If the
+/// module can't be found the error contains Lua's message.
+///
+/// This is meant for modules whose value is plain data, i.e. nil, booleans,
+/// numbers, strings and tables of those. It must not be used on modules
+/// containing userdata like `vim.NIL`, which can't be converted to an
+/// [`Object`], or functions, whose references are never removed from the
+/// Lua registry since objects don't own them. Use [`require`] for modules
+/// whose value is a table with such values, and [`lua_require_function`] for
+/// modules whose value is a function.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::{api, Dictionary, FromObject};
+///
+/// let config = Dictionary::from_obj(api::lua_require("my_plugin.config")?)?;
+/// ```
+pub fn lua_require(module: &str) -> Result<Object> {
+    self::lua_require_value(module)
+}
+
+/// Like [`lua_require`], but for modules whose value is a function, which is
+/// returned as a [`LuaFunction`] owning its reference in the Lua registry.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api;
+///
+/// let setup = api::lua_require_function("my_plugin.setup")?;
+/// setup.call::<_, ()>(())?;
+/// ```
+pub fn lua_require_function(module: &str) -> Result<LuaFunction> {
+    self::lua_require_value(module)
+}
+
+fn lua_require_value<R: Poppable>(module: &str) -> Result<R> {
+    let require = unsafe {
+        lua::with_state(|lstate| {
+            lua::ffi::lua_getglobal(lstate, cstr!("require"));
+            Function::<nvim::String, R>::pop(lstate)
+        })
+    }?;
+    let module = require.call(nvim::String::from(module));
+    require.remove_from_lua_registry();
+    Ok(module?)
+}
+
 /// Binding to [`nvim_parse_cmd`](https://neovim.io/doc/user/api.html#nvim_parse_cmd()).
 ///
 /// Parses the command line.
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use nvim_types::{self as nvim, Object};
+
+use crate::opts::SetExtmarkOpts;
+use crate::types::{ExtmarkId, Namespace};
+use crate::{Buffer, Result};
+
+/// A layer of virtual text (e.g. inlay hints) drawn in a buffer.
+///
+/// The layer owns at most one extmark per line in the given namespace, so
+/// setting the virtual text of a line twice replaces the previous chunks
+/// instead of stacking a second extmark on top of them.
+#[derive(Clone, Debug)]
+pub struct VirtualTextLayer {
+    buffer: Buffer,
+    ns_id: Namespace,
+    extmarks: HashMap<usize, ExtmarkId>,
+}
+
+impl VirtualTextLayer {
+    /// Creates a new empty layer drawing in `buffer` using the namespace with
+    /// id `ns_id`.
+    pub fn new(buffer: Buffer, ns_id: impl Into<Namespace>) -> Self {
+        Self { buffer, ns_id: ns_id.into(), extmarks: HashMap::new() }
+    }
+
+    /// Sets the virtual text displayed at the end of the 0-indexed `line`,
+    /// replacing the one previously set on that line, if any.
+    pub fn set<Txt, Hl, Hls, Cnk>(
+        &mut self,
+        line: usize,
+        chunks: Cnk,
+    ) -> Result<ExtmarkId>
+    where
+        Cnk: IntoIterator<Item = (Txt, Hls)>,
+        Txt: Into<nvim::String>,
+        Hls: IntoIterator<Item = Hl>,
+        Hl: Into<Object>,
+    {
+        let mut opts = SetExtmarkOpts::builder();
+        opts.virt_text(chunks);
+
+        if let Some(&id) = self.extmarks.get(&line) {
+            opts.id(id);
+        }
+
+        let id =
+            self.buffer.set_extmark(self.ns_id, line, 0, &opts.build())?;
+        self.extmarks.insert(line, id);
+        Ok(id)
+    }
+
+    /// Removes the virtual text set on the 0-indexed `line`. Returns whether
+    /// there was any.
+    pub fn clear(&mut self, line: usize) -> Result<bool> {
+        match self.extmarks.remove(&line) {
+            Some(id) => self.buffer.del_extmark(self.ns_id, id).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Removes all the virtual text set by this layer.
+    pub fn clear_all(&mut self) -> Result<()> {
+        for (_, id) in self.extmarks.drain() {
+            self.buffer.del_extmark(self.ns_id, id)?;
+        }
+        Ok(())
+    }
+}
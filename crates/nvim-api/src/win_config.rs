@@ -45,4 +45,38 @@ impl Window {
         unsafe { nvim_win_set_config(self.0, &config.into(), &mut err) };
         err.into_err_or_else(|| ())
     }
+
+    /// Moves a floating window in front of all the other floating windows in
+    /// its tabpage, by setting its `zindex` to one more than the highest
+    /// `zindex` among them. Does nothing if the window is already on top.
+    pub fn raise(&mut self) -> Result<()> {
+        let own = self.get_config()?.zindex.unwrap_or_default();
+
+        let mut top = None;
+        for win in self.get_tabpage()?.list_wins()? {
+            if win != *self && win.is_floating()? {
+                top = top.max(win.get_config()?.zindex);
+            }
+        }
+
+        match top {
+            Some(top) if top >= own => {
+                let config = WindowConfig {
+                    zindex: Some(top + 1),
+                    ..Default::default()
+                };
+                self.set_config(&config)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether this is a floating window. Configs of non-floating windows
+    /// can't be deserialized into a [`WindowConfig`], so this uses
+    /// `win_gettype()` instead of [`Window::get_config`].
+    pub(crate) fn is_floating(&self) -> Result<bool> {
+        let ty =
+            crate::call_function::<_, String>("win_gettype", (self.clone(),))?;
+        Ok(ty == "popup")
+    }
 }
@@ -1,28 +1,44 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use luajit_bindings::{self as lua, Poppable, Pushable};
 use nvim_types::{
     self as nvim,
     Array,
+    Dictionary,
     FromObject,
     FromObjectError,
     Function,
     Integer,
     Object,
     ToObject,
+    ToObjectResult,
     WinHandle,
 };
 use serde::{Deserialize, Serialize};
 
 use super::ffi::window::*;
+use super::opts::EvalStatuslineOpts;
+use super::types::{
+    window_options,
+    CursorPosition,
+    StatuslineInfos,
+    WinPosition,
+    WinView,
+    WindowOption,
+};
 use super::LUA_INTERNAL_CALL;
 use super::{Buffer, TabPage};
-use crate::Result;
+use crate::{Error, Result};
 
-#[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 /// A newtype struct wrapping a Neovim window. All the `nvim_win_*` functions
 /// taking a window handle as their first argument are implemented as methods
 /// on this object.
+///
+/// Like [`Buffer`]s, windows (de)serialize to their integer handle, which is
+/// only valid within the current Neovim session.
+#[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Window(pub(crate) WinHandle);
 
 impl fmt::Debug for Window {
@@ -55,6 +71,12 @@ impl From<&Window> for Object {
     }
 }
 
+impl ToObject for Window {
+    fn to_obj(self) -> ToObjectResult {
+        Ok(self.0.into())
+    }
+}
+
 impl FromObject for Window {
     fn from_obj(obj: Object) -> std::result::Result<Self, FromObjectError> {
         Ok(WinHandle::from_obj(obj)?.into())
@@ -104,6 +126,17 @@ impl Window {
         err.into_err_or_else(|| ())
     }
 
+    /// Like [`close`](Window::close) with `force` set to `true`, but it's a
+    /// no-op if the window is no longer valid, e.g. because the user has
+    /// already closed it. Useful in cleanup code like `Drop` implementations.
+    pub fn close_if_valid(self) -> Result<()> {
+        if self.is_valid() {
+            self.close(true)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Binding to [`nvim_win_del_var`](https://neovim.io/doc/user/api.html#nvim_win_del_var()).
     ///
     /// Removes a window-scoped (`w:`) variable.
@@ -114,6 +147,44 @@ impl Window {
         err.into_err_or_else(|| ())
     }
 
+    /// Shorthand for [`api::eval_statusline`](crate::eval_statusline).
+    ///
+    /// Evaluates the window's `statusline` option (or its `winbar` if
+    /// [`use_winbar`](crate::opts::EvalStatuslineOptsBuilder::use_winbar) is
+    /// set) using the window as context. The
+    /// [`window`](crate::opts::EvalStatuslineOptsBuilder::window) field of
+    /// `opts` is ignored.
+    pub fn eval_statusline(
+        &self,
+        opts: &EvalStatuslineOpts,
+    ) -> Result<StatuslineInfos> {
+        #[cfg(any(feature = "neovim-0-8", feature = "neovim-nightly"))]
+        let option = match opts.use_winbar {
+            Some(true) => "winbar",
+            _ => "statusline",
+        };
+
+        #[cfg(feature = "neovim-0-7")]
+        let option = "statusline";
+
+        // The option is global-local, so fall back to the global value if the
+        // window doesn't set one.
+        let str = match self.get_option::<String>(option)? {
+            str if str.is_empty() => crate::get_option::<String>(option)?,
+            str => str,
+        };
+
+        let mut opts = opts.clone();
+        opts.window = Some(self.clone());
+        crate::eval_statusline(&str, &opts)
+    }
+
+    /// Makes this the current window, moving the cursor to it. Same as
+    /// [`api::set_current_win`](crate::set_current_win).
+    pub fn focus(&mut self) -> Result<()> {
+        crate::set_current_win(self)
+    }
+
     /// Binding to [`nvim_win_get_buf`](https://neovim.io/doc/user/api.html#nvim_win_get_buf()).
     ///
     /// Gets the current [`Buffer`] in the window.
@@ -125,15 +196,20 @@ impl Window {
 
     /// Binding to [`nvim_win_get_cursor`](https://neovim.io/doc/user/api.html#nvim_win_get_cursor()).
     ///
-    /// Gets the (1,0)-indexed cursor position in the window.
-    pub fn get_cursor(&self) -> Result<(usize, usize)> {
+    /// Gets the (1,0)-indexed cursor position in the window. Returns an
+    /// [`Error::InvalidWindow`] if the window has been closed.
+    pub fn get_cursor(&self) -> Result<CursorPosition> {
+        if !self.is_valid() {
+            return Err(Error::InvalidWindow(self.clone()));
+        }
+
         let mut err = nvim::Error::new();
         let arr = unsafe { nvim_win_get_cursor(self.0, &mut err) };
         err.into_err_or_flatten(|| {
             let mut iter = arr.into_iter();
-            let line = usize::from_obj(iter.next().unwrap())?;
+            let row = usize::from_obj(iter.next().unwrap())?;
             let col = usize::from_obj(iter.next().unwrap())?;
-            Ok((line, col))
+            Ok(CursorPosition { row, col })
         })
     }
 
@@ -173,17 +249,36 @@ impl Window {
     /// Binding to [`nvim_win_get_position`](https://neovim.io/doc/user/api.html#nvim_win_get_position()).
     ///
     /// Gets the window position in display cells.
-    pub fn get_position(&self) -> Result<(usize, usize)> {
+    pub fn get_position(&self) -> Result<WinPosition> {
         let mut err = nvim::Error::new();
         let arr = unsafe { nvim_win_get_position(self.0, &mut err) };
         err.into_err_or_flatten(|| {
             let mut iter = arr.into_iter();
-            let line = usize::from_obj(iter.next().unwrap())?;
+            let row = usize::from_obj(iter.next().unwrap())?;
             let col = usize::from_obj(iter.next().unwrap())?;
-            Ok((line, col))
+            Ok(WinPosition { row, col })
         })
     }
 
+    /// Gets the position of the window relative to the top-left corner of
+    /// the `to` window, in display cells. Useful to place a floating window
+    /// next to another one, e.g. a submenu next to its parent menu.
+    ///
+    /// Returns an error if the window starts above or to the left of `to`.
+    pub fn get_position_relative(&self, to: &Window) -> Result<WinPosition> {
+        let pos = self.get_position()?;
+        let origin = to.get_position()?;
+        match (
+            pos.row.checked_sub(origin.row),
+            pos.col.checked_sub(origin.col),
+        ) {
+            (Some(row), Some(col)) => Ok(WinPosition { row, col }),
+            _ => Err(Error::custom(format!(
+                "{self} starts above or to the left of {to}"
+            ))),
+        }
+    }
+
     /// Binding to [`nvim_win_get_tabpage`](https://neovim.io/doc/user/api.html#nvim_win_get_tabpage()).
     ///
     /// Gets the window's `TabPage`.
@@ -232,6 +327,53 @@ impl Window {
         unsafe { nvim_win_is_valid(self.0) }
     }
 
+    /// Closes every other window in the window's tabpage, like `:only`.
+    ///
+    /// Windows that can't be closed, e.g. because their buffer has unsaved
+    /// changes, are left open: the remaining windows are still closed and the
+    /// first error is returned at the end. Windows closed as a side effect of
+    /// closing another one (e.g. by an autocommand) are skipped.
+    pub fn only(&self) -> Result<()> {
+        let others = self
+            .get_tabpage()?
+            .list_wins()?
+            .filter(|win| win != self)
+            .collect::<Vec<_>>();
+
+        let mut res = Ok(());
+
+        for win in others {
+            if !win.is_valid() {
+                continue;
+            }
+            if let Err(err) = win.close(false) {
+                res = res.and(Err(err));
+            }
+        }
+
+        res
+    }
+
+    /// Restores a view previously saved with [`Window::save_view`] by calling
+    /// `winrestview()` in the context of the window.
+    pub fn restore_view(&mut self, view: &WinView) -> Result<()> {
+        let view = Dictionary::from_obj(view.to_obj()?)?;
+        self.call(move |_| {
+            // `winrestview()` always returns `0`.
+            crate::call_function::<_, Integer>("winrestview", (view,))
+                .map(|_| ())
+        })
+    }
+
+    /// Saves the current view of the window by calling `winsaveview()` in the
+    /// context of the window.
+    pub fn save_view(&self) -> Result<WinView> {
+        let view = self.call(|_| {
+            crate::call_function::<_, Dictionary>("winsaveview", Array::new())
+        })?;
+        Ok(WinView::from_obj(view.into())?)
+    }
+
     /// Binding to [`nvim_win_set_buf`](https://neovim.io/doc/user/api.html#nvim_win_set_buf()).
     ///
     /// Sets `buffer` as the current buffer in the window.
@@ -245,13 +387,124 @@ impl Window {
     ///
     /// Sets the (1,0)-indexed cursor in the window. This will scroll the
     /// window even if it's not the current one.
+    ///
+    /// Both coordinates are validated before moving the cursor, so if either
+    /// of them is out of bounds an error is returned and the cursor is left
+    /// untouched.
     pub fn set_cursor(&mut self, line: usize, col: usize) -> Result<()> {
+        let buf = self.get_buf()?;
+
+        if line == 0 || line > buf.line_count()? {
+            return Err(Error::custom(format!(
+                "line {line} is out of bounds"
+            )));
+        }
+
+        let line_len = buf
+            .get_lines(line - 1, line, true)?
+            .next()
+            .map(|line| line.as_bytes().len())
+            .unwrap_or_default();
+
+        if col > line_len {
+            return Err(Error::custom(format!(
+                "column {col} is out of bounds for line {line}"
+            )));
+        }
+
         let mut err = nvim::Error::new();
         let pos = Array::from_iter([line as Integer, col as Integer]);
         unsafe { nvim_win_set_cursor(self.0, pos.non_owning(), &mut err) };
         err.into_err_or_else(|| ())
     }
 
+    /// Like [`set_cursor`](Window::set_cursor), but restores the window's
+    /// scroll position (i.e. its top line, top filler lines and leftmost and
+    /// skipped columns) after moving the cursor.
+    ///
+    /// A cursor outside of the visible lines would make Neovim scroll the
+    /// window anyway on the next redraw, so if `line` is above or below the
+    /// lines currently visible (taking
+    /// [`scrolloff`](https://neovim.io/doc/user/options.html#'scrolloff') into
+    /// account) the cursor is clamped to the closest visible line instead, and
+    /// `col` is clamped to that line's length. Only lines are clamped: with
+    /// `nowrap` set, a column outside of the visible ones still scrolls the
+    /// window horizontally.
+    pub fn set_cursor_no_scroll(
+        &mut self,
+        line: usize,
+        col: usize,
+    ) -> Result<()> {
+        let buf = self.get_buf()?;
+        let line_count = buf.line_count()?;
+
+        if line == 0 || line > line_count {
+            return Err(Error::custom(format!(
+                "line {line} is out of bounds"
+            )));
+        }
+
+        let (first, last) = self.scrolloff_visible_lines(line_count)?;
+
+        let (line, col) = match line.clamp(first, last) {
+            clamped if clamped == line => (line, col),
+
+            clamped => {
+                let line_len = buf
+                    .get_lines(clamped - 1, clamped, true)?
+                    .next()
+                    .map(|line| line.as_bytes().len())
+                    .unwrap_or_default();
+
+                (clamped, col.min(line_len))
+            },
+        };
+
+        let before = self.save_view()?;
+        self.set_cursor(line, col)?;
+
+        let view = WinView {
+            topline: before.topline,
+            topfill: before.topfill,
+            leftcol: before.leftcol,
+            skipcol: before.skipcol,
+            ..self.save_view()?
+        };
+
+        self.restore_view(&view)
+    }
+
+    /// Returns the first and last visible lines of the window the cursor can
+    /// be placed on without scrolling it, i.e. the lines which are at least
+    /// `scrolloff` lines away from the top and bottom of the window unless
+    /// the window shows the start or end of the buffer.
+    fn scrolloff_visible_lines(
+        &self,
+        line_count: usize,
+    ) -> Result<(usize, usize)> {
+        let top = crate::call_function::<_, usize>("line", ("w0", self))?;
+        let bottom = crate::call_function::<_, usize>("line", ("w$", self))?;
+
+        // The option is global-local, and Neovim never uses a value bigger
+        // than half of the window's height.
+        let scrolloff = match self.get_option::<Integer>("scrolloff")? {
+            n if n < 0 => crate::get_option::<Integer>("scrolloff")?,
+            n => n,
+        };
+        let max = (self.get_height()?.saturating_sub(1) / 2) as usize;
+        let scrolloff = usize::try_from(scrolloff)?.min(max);
+
+        let first = if top <= 1 { 1 } else { top + scrolloff };
+
+        let last = if bottom >= line_count {
+            line_count
+        } else {
+            bottom.saturating_sub(scrolloff)
+        };
+
+        Ok((first, last.max(first)))
+    }
+
     /// Binding to [`nvim_win_set_height`](https://neovim.io/doc/user/api.html#nvim_win_set_height()).
     ///
     /// Sets the window height.
@@ -312,3 +565,66 @@ impl Window {
         err.into_err_or_else(|| ())
     }
 }
+
+macro_rules! bool_option_setter {
+    ($fn_name:ident, $option:literal) => {
+        #[doc = concat!("Shorthand for setting the `", $option, "` option.")]
+        #[inline(always)]
+        pub fn $fn_name(&mut self, value: bool) -> Result<()> {
+            self.set_option($option, value)
+        }
+    };
+}
+
+impl Window {
+    /// Gets the value of a typed window option, e.g.
+    /// `win.get_opt::<window_options::Number>()` returns a `bool`.
+    pub fn get_opt<Opt: WindowOption>(&self) -> Result<Opt::Value> {
+        self.get_option(Opt::NAME)
+    }
+
+    /// Sets the value of a typed window option, e.g.
+    /// `win.set_opt::<window_options::Number>(true)`.
+    pub fn set_opt<Opt: WindowOption>(
+        &mut self,
+        value: Opt::Value,
+    ) -> Result<()> {
+        self.set_option(Opt::NAME, value)
+    }
+
+    /// Gets the `winhighlight` option parsed into a map from the highlight
+    /// groups being overridden to the groups replacing them.
+    pub fn get_winhighlight(&self) -> Result<HashMap<String, String>> {
+        let winhl = self.get_opt::<window_options::Winhighlight>()?;
+        Ok(winhl
+            .split(',')
+            .filter_map(|entry| entry.split_once(':'))
+            .map(|(from, to)| (from.to_owned(), to.to_owned()))
+            .collect())
+    }
+
+    /// Sets the `winhighlight` option from a map of `(from, to)` highlight
+    /// groups, replacing any previous value. The entries are sorted so that
+    /// the resulting option value is deterministic.
+    pub fn set_winhighlight<K, V>(
+        &mut self,
+        map: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<()>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut entries = map
+            .into_iter()
+            .map(|(from, to)| format!("{}:{}", from.as_ref(), to.as_ref()))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+        self.set_opt::<window_options::Winhighlight>(entries.join(","))
+    }
+
+    bool_option_setter!(set_cursorline, "cursorline");
+    bool_option_setter!(set_number, "number");
+    bool_option_setter!(set_relativenumber, "relativenumber");
+    bool_option_setter!(set_spell, "spell");
+    bool_option_setter!(set_wrap, "wrap");
+}
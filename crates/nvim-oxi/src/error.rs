@@ -1,29 +1,77 @@
+use std::fmt;
+
 use thiserror::Error as ThisError;
 
 /// Alias for a `Result` with error type [`nvim_oxi::Error`](Error).
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Error returned by `nvim-oxi` functions.
+///
+/// Errors coming from the [`api`](crate::api) functions are flattened when
+/// converted into this type, so that e.g. an error raised by Neovim during an
+/// API call can be matched directly as [`Error::Nvim`]. The ones that don't
+/// have a corresponding variant are wrapped in [`Error::Api`].
+///
+/// New variants may be added in the future, so matches on this type need a
+/// wildcard arm.
+#[non_exhaustive]
 #[derive(Clone, Debug, ThisError)]
 #[cfg_attr(not(feature = "mlua"), derive(Eq, PartialEq))]
 pub enum Error {
+    /// An error raised by Neovim.
+    #[error(transparent)]
+    Nvim(#[from] nvim_types::Error),
+
+    /// An [`Object`](crate::Object) couldn't be converted into a Rust type.
     #[error(transparent)]
-    NvimError(#[from] nvim_types::Error),
+    FromObject(#[from] nvim_types::FromObjectError),
 
+    /// A Rust type couldn't be converted into an [`Object`](crate::Object).
     #[error(transparent)]
-    FromObjectError(#[from] nvim_types::FromObjectError),
+    ToObject(#[from] nvim_types::ToObjectError),
 
+    /// Any other error returned by the [`api`](crate::api) functions.
     #[error(transparent)]
-    ToObjectError(#[from] nvim_types::ToObjectError),
+    Api(nvim_api::Error),
 
+    /// An error raised while interacting with Lua.
     #[error(transparent)]
-    ApiError(#[from] nvim_api::Error),
+    Lua(#[from] luajit_bindings::Error),
 
+    /// An error raised by libuv.
     #[cfg(feature = "libuv")]
     #[error(transparent)]
-    LibuvError(#[from] libuv_bindings::Error),
+    Libuv(#[from] libuv_bindings::Error),
 
+    /// An error raised by `mlua`.
     #[cfg(feature = "mlua")]
     #[error(transparent)]
-    MluaError(#[from] mlua::Error),
+    Mlua(#[from] mlua::Error),
+
+    /// A catch-all for errors not covered by the other variants, e.g. ones
+    /// created by plugins with [`Error::other`].
+    #[error("{0}")]
+    Other(String),
+}
+
+impl Error {
+    /// Creates an [`Error::Other`] from any displayable message.
+    pub fn other(msg: impl fmt::Display) -> Self {
+        Self::Other(msg.to_string())
+    }
+}
+
+impl From<nvim_api::Error> for Error {
+    fn from(err: nvim_api::Error) -> Self {
+        use nvim_api::Error::*;
+
+        match err {
+            NvimError(err) => Self::Nvim(err),
+            FromObjectError(err) => Self::FromObject(err),
+            ToObjectError(err) => Self::ToObject(err),
+            LuaError(err) => Self::Lua(err),
+            Other(msg) => Self::Other(msg),
+            other => Self::Api(other),
+        }
+    }
 }
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use libuv_bindings::TimerHandle;
+
+use crate::api::{self, types::FeedkeysMode};
+use crate::Result;
+
+/// Feeds a script of keystrokes to Neovim, pausing between each step.
+///
+/// Special keys like `<Esc>` are translated with
+/// [`api::replace_termcodes`](crate::api::replace_termcodes). The keys of
+/// every step are then executed with
+/// [`api::feedkeys_and_wait`](crate::api::feedkeys_and_wait), then the next
+/// step is scheduled on a [`TimerHandle`] firing after the step's delay,
+/// giving autocommands and other callbacks time to run in between.
+///
+/// Only the first step is executed before this function returns, so only
+/// errors coming from it are returned. Errors raised by the following steps
+/// abort the rest of the script and are reported by
+/// [`schedule`](crate::schedule).
+///
+/// # Examples
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use nvim_oxi::api;
+///
+/// api::feed_script(&[
+///     ("ihello world<Esc>", Duration::from_millis(100)),
+///     ("u", Duration::ZERO),
+/// ])?;
+/// ```
+pub fn feed_script(steps: &[(&str, Duration)]) -> Result<()> {
+    let steps = steps
+        .iter()
+        .map(|&(keys, delay)| {
+            (api::replace_termcodes(keys, true, false, true), delay)
+        })
+        .collect();
+
+    feed_steps(steps)
+}
+
+fn feed_steps(mut steps: VecDeque<(crate::String, Duration)>) -> Result<()> {
+    let (keys, delay) = match steps.pop_front() {
+        Some(step) => step,
+        None => return Ok(()),
+    };
+
+    api::feedkeys_and_wait(keys, FeedkeysMode::Remap)?;
+
+    if !steps.is_empty() {
+        // API functions can't be called from libuv callbacks, so the next
+        // step is scheduled on the main loop.
+        let _ = TimerHandle::once(delay, move || {
+            crate::schedule(move |_| feed_steps(steps));
+            Ok::<_, crate::Error>(())
+        })?;
+    }
+
+    Ok(())
+}
@@ -15,11 +15,18 @@
 #[doc(hidden)]
 pub mod entrypoint;
 mod error;
+#[cfg(feature = "libuv")]
+mod feed_script;
+mod macros;
 mod toplevel;
 
 pub mod api {
     #[doc(inline)]
     pub use nvim_api::*;
+
+    #[cfg(feature = "libuv")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "libuv")))]
+    pub use crate::feed_script::feed_script;
 }
 
 #[doc(hidden)]
@@ -35,6 +42,10 @@ pub mod libuv {
     pub use libuv_bindings::*;
 }
 
+#[cfg(feature = "logging")]
+#[cfg_attr(docsrs, doc(cfg(feature = "logging")))]
+pub mod logging;
+
 #[cfg(feature = "mlua")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mlua")))]
 pub mod mlua {
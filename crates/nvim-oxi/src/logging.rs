@@ -0,0 +1,111 @@
+//! Integration with the [`log`](https://docs.rs/log) crate.
+//!
+//! Once [`OxiLogger::init`] has been called, the `log::error!`, `log::warn!`,
+//! etc. macros will write their records to the Neovim message area.
+
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use once_cell::sync::Lazy;
+
+use crate::api::{self, types::LogLevel};
+use crate::lua;
+
+static LOGGER: OxiLogger = OxiLogger;
+
+/// The records logged from threads other than the main one, which are written
+/// the next time a record is logged or the logger is flushed from the main
+/// thread.
+static PENDING: Lazy<Mutex<Vec<(Level, String)>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// A [`Log`] implementation writing to the Neovim message area.
+///
+/// Records with an [`Error`](log::Level::Error) level are written with
+/// [`api::err_writeln`], all the others with [`api::out_write`].
+///
+/// The Neovim API can only be called from the main thread, so the records
+/// logged from other threads (e.g. by a dependency's worker threads) are
+/// queued instead. They're written, in order, before the next record logged
+/// from the main thread, or when calling `log::logger().flush()` from it.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OxiLogger;
+
+impl OxiLogger {
+    /// Registers the logger via [`log::set_logger`], discarding all the
+    /// records less severe than `level`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use nvim_oxi::{api::types::LogLevel, logging::OxiLogger};
+    ///
+    /// OxiLogger::init(LogLevel::Info)?;
+    /// log::info!("Hello from Rust!");
+    /// ```
+    pub fn init(level: LogLevel) -> Result<(), SetLoggerError> {
+        log::set_logger(&LOGGER)?;
+        log::set_max_level(level_filter(level));
+        Ok(())
+    }
+}
+
+impl Log for OxiLogger {
+    #[inline]
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let msg = format!("[{}] {}", record.level(), record.args());
+
+        if !lua::is_main_thread() {
+            if let Ok(mut pending) = PENDING.lock() {
+                pending.push((record.level(), msg));
+            }
+            return;
+        }
+
+        self.flush();
+        write(record.level(), msg);
+    }
+
+    fn flush(&self) {
+        if !lua::is_main_thread() {
+            return;
+        }
+
+        let pending = match PENDING.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return,
+        };
+
+        for (level, msg) in pending {
+            write(level, msg);
+        }
+    }
+}
+
+/// Writes a formatted record to the Neovim message area.
+fn write(level: Level, msg: String) {
+    match level {
+        Level::Error => api::err_writeln(&msg),
+        _ => api::out_write(msg + "\n"),
+    }
+}
+
+/// Converts one of Neovim's `vim.log.levels` into a [`LevelFilter`].
+fn level_filter(level: LogLevel) -> LevelFilter {
+    match level {
+        LogLevel::Trace => LevelFilter::Trace,
+        LogLevel::Debug => LevelFilter::Debug,
+        LogLevel::Info => LevelFilter::Info,
+        LogLevel::Warn => LevelFilter::Warn,
+        LogLevel::Error => LevelFilter::Error,
+        _ => LevelFilter::Off,
+    }
+}
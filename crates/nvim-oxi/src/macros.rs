@@ -0,0 +1,81 @@
+/// Returns the source of a Lua module which loads a plugin compiled with
+/// `nvim-oxi` from the `runtimepath`.
+///
+/// Placing the generated code in `lua/{name}.lua` next to the compiled library
+/// (i.e. `lua/lib{name}.so`, `lua/lib{name}.dylib` or `lua/{name}.dll`) lets
+/// users `require("{name}")` the plugin without having to rename the library.
+/// The library is located with `nvim_get_runtime_file()` and its
+/// `luaopen_{name}` function, generated by [`module`](crate::module), is
+/// called.
+///
+/// When called with the name of the module as a string literal, the macro
+/// expands to a `&'static str`, so it can also be used to initialize a
+/// `const` or a `static`.
+///
+/// When called without arguments, the name of the module defaults to the name
+/// of the package the macro is invoked in, with dashes replaced by
+/// underscores like Cargo does for the library's file name. Unlike
+/// `CARGO_CRATE_NAME`, this also works in build scripts, whose crate is always
+/// called `build_script_build`. Note that:
+///
+/// - since the dashes are replaced at runtime, this form returns a `String`
+///   instead of a `&'static str`;
+///
+/// - the package name is used even if the crate sets a custom `name` in its
+///   `[lib]` section, in which case the name has to be passed explicitly.
+///
+/// Either way, the name should match the name of the function annotated with
+/// `#[nvim_oxi::module]`.
+///
+/// # Examples
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     std::fs::write("lua/foo.lua", nvim_oxi::generate_lua_shim!("foo"))
+///         .unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! generate_lua_shim {
+    () => {
+        ::std::format!(
+            ::core::concat!(
+                "-- Generated by nvim-oxi, do not edit.\n",
+                "local name = \"{}\"\n",
+                $crate::generate_lua_shim!(@body)
+            ),
+            ::core::env!("CARGO_PKG_NAME").replace('-', "_")
+        )
+    };
+
+    (@body) => {
+        r#"
+local prefix, suffix = "lib", ".so"
+if jit.os == "OSX" then
+  suffix = ".dylib"
+elseif jit.os == "Windows" then
+  prefix, suffix = "", ".dll"
+end
+
+local filename = prefix .. name .. suffix
+local path = vim.api.nvim_get_runtime_file("lua/" .. filename, false)[1]
+if path == nil then
+  error(("couldn't find %q in the runtimepath"):format(filename))
+end
+
+local luaopen = assert(package.loadlib(path, "luaopen_" .. name))
+return luaopen()
+"#
+    };
+
+    ($name:literal) => {
+        ::core::concat!(
+            "-- Generated by nvim-oxi, do not edit.\n",
+            "local name = \"",
+            $name,
+            "\"\n",
+            $crate::generate_lua_shim!(@body)
+        )
+    };
+}
@@ -1,7 +1,7 @@
 use luajit_bindings::{self as lua, ffi::*, macros::cstr};
 use nvim_types::Function;
 
-use crate::Result;
+use crate::{api, Result};
 
 /// Same as [`print!`] but for the [`std::dbg!`] macro
 ///
@@ -31,11 +31,56 @@ macro_rules! dbg {
     };
 }
 
+/// Installs a panic hook which reports panics to Neovim.
+///
+/// By default panic messages are written to stderr, which isn't visible from
+/// within Neovim. After calling this function the message and location of
+/// every panic are written with [`api::err_writeln`] instead, making them
+/// show up in `:messages`.
+///
+/// The Neovim API can only be called from the main thread, so panics on other
+/// threads are still handled by the hook that was set before calling this.
+///
+/// Note that this doesn't prevent the panic from unwinding: if it crosses an
+/// FFI boundary it will still abort the process, so callbacks that may panic
+/// should use [`std::panic::catch_unwind`].
+pub fn set_panic_hook() {
+    let prev_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        if !lua::is_main_thread() {
+            return prev_hook(info);
+        }
+
+        let payload = info.payload();
+
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+
+        match info.location() {
+            Some(loc) => {
+                api::err_writeln(&format!("panicked at '{msg}', {loc}"))
+            },
+            None => api::err_writeln(&format!("panicked at '{msg}'")),
+        }
+    }));
+}
+
 /// Binding to `vim.schedule`.
 ///
 /// Schedules a callback to be invoked soon by the main event-loop. Useful to
 /// avoid [`textlock`](https://neovim.io/doc/user/eval.html#textlock) or other
 /// temporary restrictions.
+///
+/// The callback is never invoked synchronously, not even when `schedule` is
+/// called from within another scheduled callback: callbacks are queued and
+/// run in the order they were scheduled, so a nested callback runs after the
+/// outer one has returned and after every callback that was already in the
+/// queue. This makes it safe to chain scheduled work without growing the
+/// stack.
 pub fn schedule<F>(fun: F)
 where
     F: FnOnce(()) -> Result<()> + 'static,
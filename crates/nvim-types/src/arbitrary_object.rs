@@ -0,0 +1,134 @@
+//! Implementation of [`Arbitrary`] for [`Object`], used to fuzz the
+//! conversions between objects and other types.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Array, Dictionary, Object};
+
+/// The maximum nesting level of the generated arrays and dictionaries.
+const MAX_DEPTH: usize = 6;
+
+/// The maximum number of elements in the generated arrays and dictionaries.
+const MAX_LEN: usize = 8;
+
+/// Generates trees of nils, booleans, integers, floats, strings, arrays and
+/// dictionaries. Lua references are never generated since they'd have to
+/// point to valid entries in the Lua registry, and floats are never `NaN` so
+/// that the generated objects can be compared for equality.
+impl<'a> Arbitrary<'a> for Object {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_object(u, MAX_DEPTH)
+    }
+}
+
+fn arbitrary_object(u: &mut Unstructured<'_>, depth: usize) -> Result<Object> {
+    // Only generate scalars once the maximum depth has been reached.
+    let max_kind = if depth == 0 { 4 } else { 6 };
+
+    let obj = match u.int_in_range(0..=max_kind)? {
+        0 => Object::nil(),
+
+        1 => bool::arbitrary(u)?.into(),
+
+        2 => i64::arbitrary(u)?.into(),
+
+        3 => match f64::arbitrary(u)? {
+            float if float.is_nan() => 0.0.into(),
+            float => float.into(),
+        },
+
+        4 => crate::String::from_bytes(Vec::arbitrary(u)?).into(),
+
+        5 => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            (0..len)
+                .map(|_| arbitrary_object(u, depth - 1))
+                .collect::<Result<Array>>()?
+                .into()
+        },
+
+        6 => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            (0..len)
+                .map(|_| {
+                    let key = crate::String::from_bytes(Vec::arbitrary(u)?);
+                    Ok((key, arbitrary_object(u, depth - 1)?))
+                })
+                .collect::<Result<Dictionary>>()?
+                .into()
+        },
+
+        _ => unreachable!(),
+    };
+
+    Ok(obj)
+}
+
+/// Runs `test` on `iterations` objects generated from a deterministic stream
+/// of pseudo-random bytes. Used by this crate's tests and by the integration
+/// tests, which need a running Neovim.
+#[doc(hidden)]
+pub fn fuzz_objects(iterations: usize, mut test: impl FnMut(Object)) {
+    // xorshift64, seeded with a fixed value so that failures are
+    // reproducible.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut bytes = vec![0; 4096];
+
+    for _ in 0..iterations {
+        for byte in &mut bytes {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *byte = state as u8;
+        }
+        let mut u = Unstructured::new(&bytes);
+        test(Object::arbitrary(&mut u).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clone_roundtrip() {
+        fuzz_objects(500, |obj| {
+            let cloned = obj.clone();
+            assert_eq!(obj, cloned);
+            drop(obj);
+            // The clone must be fully independent of the original.
+            assert_eq!(cloned, cloned.clone());
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_roundtrip() {
+        use serde::Deserialize;
+
+        fuzz_objects(500, |obj| {
+            let deserializer = crate::Deserializer::new(obj.clone());
+            let deserialized = Object::deserialize(deserializer).unwrap();
+            assert_eq!(obj, deserialized);
+        });
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_roundtrip() {
+        fuzz_objects(500, |obj| {
+            let bytes = obj.to_msgpack();
+            assert_eq!(obj, Object::from_msgpack(&bytes).unwrap());
+        });
+    }
+
+    #[test]
+    fn deeply_nested() {
+        let mut obj = Object::from(Dictionary::from_iter([("leaf", 42)]));
+        for depth in 0..100 {
+            obj = Array::from_iter([obj, Object::from(depth)]).into();
+            obj = Dictionary::from_iter([("inner", obj)]).into();
+        }
+        assert_eq!(obj, obj.clone());
+    }
+}
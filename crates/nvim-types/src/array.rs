@@ -1,11 +1,13 @@
 use std::ffi::c_int;
 use std::mem::ManuallyDrop;
+use std::ops::Range;
 use std::{fmt, ptr};
 
 use lua::{ffi::*, Poppable, Pushable};
 use luajit_bindings as lua;
 
-use super::{Collection, Object};
+use super::object::DisplayAsDebug;
+use super::{Collection, Integer, Object};
 
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L95
 //
@@ -14,13 +16,13 @@ pub type Array = Collection<Object>;
 
 impl fmt::Debug for Array {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, f)
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
 impl fmt::Display for Array {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+        f.debug_list().entries(self.iter().map(DisplayAsDebug)).finish()
     }
 }
 
@@ -71,6 +73,15 @@ where
     }
 }
 
+impl Array {
+    /// Creates an array of integers from a byte slice, one integer per byte.
+    /// The bytes can be decoded back with `Vec::<u8>::from_obj`, which
+    /// errors if any of the integers doesn't fit in a `u8`.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        bytes.iter().map(|&byte| byte as i64).collect()
+    }
+}
+
 /// An owning iterator over the entries of
 pub struct ArrayIterator {
     start: *const Object,
@@ -161,6 +172,22 @@ impl_from_tuple!(A B C D E F G H I J K L M N);
 impl_from_tuple!(A B C D E F G H I J K L M N O);
 impl_from_tuple!(A B C D E F G H I J K L M N O P);
 
+/// Converts a range into a `[start, end]` array, like the ones taken by many
+/// API functions.
+///
+/// # Panics
+///
+/// Panics if either bound doesn't fit in an [`Integer`].
+impl From<Range<usize>> for Array {
+    fn from(range: Range<usize>) -> Self {
+        let bound = |n: usize| {
+            Integer::try_from(n)
+                .expect("range bound doesn't fit in an Integer")
+        };
+        Self::from((bound(range.start), bound(range.end)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +203,62 @@ mod tests {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn iter_by_ref() {
+        let mut array = Array::from((1, "foo", true));
+
+        let kinds = array.iter().map(Object::kind).collect::<Vec<_>>();
+        assert_eq!(kinds, array.iter().map(Object::kind).collect::<Vec<_>>());
+
+        for obj in array.iter_mut() {
+            *obj = Object::from(42);
+        }
+        assert_eq!(Array::from((42, 42, 42)), array);
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        use crate::FromObject;
+
+        let bytes = [0, 1, 42, 255];
+        let array = Array::from_bytes(&bytes);
+        assert_eq!(Array::from((0, 1, 42, 255)), array);
+
+        let decoded = Vec::<u8>::from_obj(array.into());
+        assert_eq!(Ok(bytes.to_vec()), decoded);
+
+        let out_of_range = Object::from(Array::from((1, 256)));
+        assert!(Vec::<u8>::from_obj(out_of_range).is_err());
+    }
+
+    #[test]
+    fn tuple_roundtrip() {
+        use crate::{FromObject, FromObjectError, ToObject};
+
+        let obj = (1, "two", true).to_obj().unwrap();
+        assert_eq!(Object::from(Array::from((1, "two", true))), obj);
+
+        let tuple = <(i64, String, bool)>::from_obj(obj.clone());
+        assert_eq!(Ok((1, "two".to_owned(), true)), tuple);
+
+        let wrong_length = <(i64, String)>::from_obj(obj);
+        assert_eq!(
+            Err(FromObjectError::WrongLength { expected: 2, actual: 3 }),
+            wrong_length
+        );
+    }
+
+    #[test]
+    fn from_range() {
+        let array = Array::from(0..5);
+        assert_eq!(2, array.len());
+        assert_eq!(Array::from((0, 5)), array);
+
+        let mut iter = array.into_iter();
+        assert_eq!(Some(Object::from(0)), iter.next());
+        assert_eq!(Some(Object::from(5)), iter.next());
+    }
+
     #[test]
     fn drop_iter_halfway() {
         let array = Array::from_iter(["Foo", "Bar", "Baz"]);
@@ -190,6 +273,14 @@ mod tests {
         assert_eq!(0, empty.into_iter().count());
     }
 
+    #[test]
+    fn retain_integers() {
+        let mut array = Array::from((1, "foo", 2, true, 3.0, 4));
+        array.retain(|obj| obj.kind() == crate::ObjectKind::Integer);
+        assert_eq!(3, array.len());
+        assert_eq!(Array::from((1, 2, 4)), array);
+    }
+
     #[test]
     fn debug_array() {
         let arr = Array::from((1, 2, 3, "a", true));
@@ -44,6 +44,52 @@ impl<T> Collection<T> {
         self.len() == 0
     }
 
+    /// Returns an iterator over references to the items, without consuming
+    /// the collection.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns an iterator over mutable references to the items, without
+    /// consuming the collection.
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Retains only the items for which `f` returns `true`, dropping all the
+    /// others. The order of the retained items is preserved.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+
+        // Set the size to zero while shifting the items so that if `f` panics
+        // the remaining items are leaked instead of being dropped twice.
+        self.size = 0;
+
+        let mut retained = 0;
+
+        for idx in 0..len {
+            unsafe {
+                let current = self.items.add(idx);
+                if f(&*current) {
+                    if retained != idx {
+                        let hole = self.items.add(retained);
+                        ptr::copy_nonoverlapping(current, hole, 1);
+                    }
+                    retained += 1;
+                } else {
+                    ptr::drop_in_place(current);
+                }
+            }
+        }
+
+        self.size = retained;
+    }
+
     #[inline]
     pub(crate) fn as_slice(&self) -> &[T] {
         if self.items.is_null() {
@@ -55,7 +101,11 @@ impl<T> Collection<T> {
 
     #[inline]
     pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
-        unsafe { slice::from_raw_parts_mut(self.items, self.size) }
+        if self.items.is_null() {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.items, self.size) }
+        }
     }
 
     #[inline]
@@ -112,6 +162,26 @@ impl<T> DerefMut for Collection<T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a Collection<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Collection<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<T> From<Vec<T>> for Collection<T> {
     #[inline]
     fn from(vec: Vec<T>) -> Self {
@@ -135,3 +205,57 @@ impl<T> From<Collection<T>> for Vec<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn retain_drops_removed_items() {
+        let item = Rc::new(());
+        let mut coll =
+            Collection::from(vec![item.clone(), item.clone(), item.clone()]);
+        assert_eq!(4, Rc::strong_count(&item));
+
+        let mut idx = 0;
+        coll.retain(|_| {
+            idx += 1;
+            idx != 2
+        });
+        assert_eq!(2, coll.len());
+        assert_eq!(3, Rc::strong_count(&item));
+
+        drop(coll);
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn iter_mut_empty() {
+        let mut coll = Collection::<Rc<()>>::new();
+        assert_eq!(0, coll.iter_mut().count());
+    }
+
+    #[test]
+    fn iter_by_ref() {
+        let mut coll = Collection::from(vec![1, 2, 3]);
+
+        for item in &mut coll {
+            *item *= 2;
+        }
+
+        let mut sum = 0;
+        for item in &coll {
+            sum += item;
+        }
+        assert_eq!(12, sum);
+    }
+
+    #[test]
+    fn retain_empty() {
+        let mut coll = Collection::<Rc<()>>::new();
+        coll.retain(|_| true);
+        assert!(coll.is_empty());
+    }
+}
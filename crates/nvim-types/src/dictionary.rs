@@ -6,6 +6,7 @@ use std::{fmt, ptr};
 use lua::{ffi::*, Poppable, Pushable};
 use luajit_bindings as lua;
 
+use super::object::DisplayAsDebug;
 use super::{Collection, Object, String};
 
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L95
@@ -21,6 +22,26 @@ pub struct KeyValuePair {
     pub(crate) value: Object,
 }
 
+impl KeyValuePair {
+    /// The key of the pair.
+    #[inline]
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    /// The value of the pair.
+    #[inline]
+    pub fn value(&self) -> &Object {
+        &self.value
+    }
+
+    /// A mutable reference to the value of the pair.
+    #[inline]
+    pub fn value_mut(&mut self) -> &mut Object {
+        &mut self.value
+    }
+}
+
 impl fmt::Debug for KeyValuePair {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(self, f)
@@ -44,6 +65,14 @@ where
 }
 
 impl Dictionary {
+    /// Returns an iterator over the `(key, value)` pairs of the dictionary,
+    /// without taking ownership of them. Keys that aren't valid UTF-8 are
+    /// skipped.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Object)> + '_ {
+        self.iter()
+            .filter_map(|pair| Some((pair.key.as_str().ok()?, &pair.value)))
+    }
+
     pub fn get<Q>(&self, query: &Q) -> Option<&Object>
     where
         String: PartialEq<Q>,
@@ -71,7 +100,12 @@ impl fmt::Debug for Dictionary {
 
 impl fmt::Display for Dictionary {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        f.debug_map()
+            .entries(
+                self.iter()
+                    .map(|pair| (&pair.key, DisplayAsDebug(&pair.value))),
+            )
+            .finish()
     }
 }
 
@@ -241,6 +275,22 @@ mod tests {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn iter_by_ref() {
+        let mut dict = Dictionary::from_iter([("foo", 1), ("bar", 2)]);
+
+        let mut keys = Vec::new();
+        for pair in &dict {
+            keys.push(pair.key().to_string_lossy().into_owned());
+        }
+        assert_eq!(vec!["foo", "bar"], keys);
+
+        for pair in &mut dict {
+            *pair.value_mut() = Object::from(42);
+        }
+        assert!(dict.iter().all(|pair| pair.value() == &Object::from(42)));
+    }
+
     #[test]
     fn drop_iter_halfway() {
         let dict = Dictionary::from_iter([
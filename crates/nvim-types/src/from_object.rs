@@ -7,20 +7,33 @@ use crate::{
     Float,
     Function,
     Integer,
+    LuaFunction,
     Object,
     ObjectKind,
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, ThisError)]
 pub enum Error {
     #[error("Was expecting a \"{expected}\" but received a \"{actual}\"")]
     WrongType { expected: &'static str, actual: &'static str },
 
+    #[error(
+        "Was expecting an array of length {expected} but received one of \
+         length {actual}"
+    )]
+    WrongLength { expected: usize, actual: usize },
+
     #[error(transparent)]
     FromInt(#[from] std::num::TryFromIntError),
 
+    #[error(
+        "Float {0} can't be converted to an integer without losing precision"
+    )]
+    FromFloat(String),
+
     #[error(transparent)]
     FromUtf8(#[from] std::string::FromUtf8Error),
 
@@ -59,11 +72,28 @@ impl FromObject for Boolean {
     }
 }
 
+/// Floats are also accepted as long as they have an integral value, since
+/// Vimscript and Lua don't always preserve the distinction between the two.
 impl FromObject for Integer {
     fn from_obj(obj: Object) -> Result<Self> {
         match obj.kind() {
             ObjectKind::Integer => Ok(unsafe { obj.as_integer_unchecked() }),
 
+            ObjectKind::Float => {
+                let float = unsafe { obj.as_float_unchecked() };
+
+                // `Integer::MAX as Float` rounds up to 2^63, which is out of
+                // range.
+                if float.fract() == 0.0
+                    && float >= Integer::MIN as Float
+                    && float < Integer::MAX as Float
+                {
+                    Ok(float as Integer)
+                } else {
+                    Err(Error::FromFloat(float.to_string()))
+                }
+            },
+
             other => Err(Error::WrongType {
                 expected: "integer",
                 actual: other.as_static(),
@@ -72,11 +102,16 @@ impl FromObject for Integer {
     }
 }
 
+/// Integers are also accepted, and converted with `as`.
 impl FromObject for Float {
     fn from_obj(obj: Object) -> Result<Self> {
         match obj.kind() {
             ObjectKind::Float => Ok(unsafe { obj.as_float_unchecked() }),
 
+            ObjectKind::Integer => {
+                Ok(unsafe { obj.as_integer_unchecked() } as Float)
+            },
+
             other => Err(Error::WrongType {
                 expected: "float",
                 actual: other.as_static(),
@@ -139,6 +174,21 @@ impl<A, R> FromObject for Function<A, R> {
     }
 }
 
+impl FromObject for LuaFunction {
+    fn from_obj(obj: Object) -> Result<Self> {
+        match obj.kind() {
+            ObjectKind::LuaRef => Ok(Self::from_borrowed_ref(unsafe {
+                obj.as_luaref_unchecked()
+            })),
+
+            other => Err(Error::WrongType {
+                expected: "function",
+                actual: other.as_static(),
+            }),
+        }
+    }
+}
+
 /// Implements `FromObject` for a type that implements `From<Integer>`.
 macro_rules! from_int {
     ($integer:ty) => {
@@ -198,3 +248,80 @@ impl<T: FromObject> FromObject for Vec<T> {
         Array::from_obj(obj)?.into_iter().map(FromObject::from_obj).collect()
     }
 }
+
+/// Implements `FromObject` for tuples, which are converted from arrays of
+/// the same length. Goes up to the same arity as `From<(..)> for Array`, so
+/// that every tuple that can be turned into an array can be converted back.
+macro_rules! impl_tuple {
+    ($len:literal; $($ty:ident)*) => {
+        impl <$($ty: FromObject),*> FromObject for ($($ty,)*) {
+            fn from_obj(obj: Object) -> Result<Self> {
+                let array = Array::from_obj(obj)?;
+
+                if array.len() != $len {
+                    return Err(Error::WrongLength {
+                        expected: $len,
+                        actual: array.len(),
+                    });
+                }
+
+                let mut iter = array.into_iter();
+
+                Ok(($($ty::from_obj(iter.next().unwrap())?,)*))
+            }
+        }
+    };
+}
+
+impl_tuple!(1; A);
+impl_tuple!(2; A B);
+impl_tuple!(3; A B C);
+impl_tuple!(4; A B C D);
+impl_tuple!(5; A B C D E);
+impl_tuple!(6; A B C D E F);
+impl_tuple!(7; A B C D E F G);
+impl_tuple!(8; A B C D E F G H);
+impl_tuple!(9; A B C D E F G H I);
+impl_tuple!(10; A B C D E F G H I J);
+impl_tuple!(11; A B C D E F G H I J K);
+impl_tuple!(12; A B C D E F G H I J K L);
+impl_tuple!(13; A B C D E F G H I J K L M);
+impl_tuple!(14; A B C D E F G H I J K L M N);
+impl_tuple!(15; A B C D E F G H I J K L M N O);
+impl_tuple!(16; A B C D E F G H I J K L M N O P);
+
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl FromObject for serde_json::Value {
+    fn from_obj(obj: Object) -> Result<Self> {
+        use serde::Deserialize;
+        Self::deserialize(crate::Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_from_float() {
+        assert_eq!(Ok(3), Integer::from_obj(3.0.into()));
+        assert_eq!(Ok(-3), Integer::from_obj((-3.0).into()));
+        assert_eq!(Ok(3), u32::from_obj(3.0.into()));
+        assert_eq!(
+            Err(Error::FromFloat("3.5".into())),
+            Integer::from_obj(3.5.into())
+        );
+        assert!(Integer::from_obj(1e19.into()).is_err());
+        assert!(Integer::from_obj(Float::NAN.into()).is_err());
+        assert!(Integer::from_obj(Float::INFINITY.into()).is_err());
+        assert!(u32::from_obj((-1.0).into()).is_err());
+    }
+
+    #[test]
+    fn float_from_integer() {
+        assert_eq!(Ok(3.0), Float::from_obj(3.into()));
+        assert_eq!(Ok(3.0), f32::from_obj(3.into()));
+        assert_eq!(Ok(3.5), Float::from_obj(3.5.into()));
+    }
+}
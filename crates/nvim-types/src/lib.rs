@@ -1,12 +1,20 @@
 #![allow(clippy::missing_safety_doc)]
 use std::ffi::{c_double, c_int};
 
+#[cfg(any(test, feature = "arbitrary"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+mod arbitrary_object;
 mod array;
 mod collection;
 mod dictionary;
 mod error;
 mod from_object;
 mod function;
+mod lua_function;
+mod lua_table;
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+mod msgpack;
 mod non_owning;
 mod object;
 mod string;
@@ -16,6 +24,9 @@ mod to_object;
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub(crate) mod serde;
 
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub use arbitrary_object::fuzz_objects;
 pub use array::{Array, ArrayIterator};
 pub(crate) use collection::*;
 pub use dictionary::{DictIterator, Dictionary};
@@ -26,6 +37,11 @@ pub use from_object::{
     Result as FromObjectResult,
 };
 pub use function::Function;
+pub use lua_function::LuaFunction;
+pub use lua_table::LuaTable;
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+pub use msgpack::Error as MsgpackError;
 #[doc(hidden)]
 pub use non_owning::NonOwning;
 pub use object::{Object, ObjectKind};
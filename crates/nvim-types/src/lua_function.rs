@@ -0,0 +1,124 @@
+use std::ffi::{c_int, c_void};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use luajit_bindings::{self as lua, ffi, Poppable, Pushable};
+
+use crate::LuaRef;
+
+/// A Lua function stored in the Lua registry, e.g. a callback passed to a
+/// plugin from Lua.
+///
+/// Unlike [`Function`](crate::Function), the types of the arguments and of
+/// the return value are chosen at every [`call`](LuaFunction::call), and the
+/// reference is removed from the registry when the `LuaFunction` is dropped.
+///
+/// It can also be extracted from an [`Object`](crate::Object) of kind
+/// [`LuaRef`](crate::ObjectKind::LuaRef), like the callbacks returned by some
+/// API functions. Since objects don't own their Lua references, the
+/// `LuaFunction` takes a new reference to the same function.
+///
+/// Two `LuaFunction`s are equal if they refer to the same Lua function, even
+/// through different registry references.
+pub struct LuaFunction {
+    lua_ref: LuaRef,
+}
+
+impl fmt::Debug for LuaFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<lua function {}>", self.lua_ref)
+    }
+}
+
+impl Clone for LuaFunction {
+    fn clone(&self) -> Self {
+        Self::from_borrowed_ref(self.lua_ref)
+    }
+}
+
+impl PartialEq for LuaFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ptr() == other.as_ptr()
+    }
+}
+
+impl Eq for LuaFunction {}
+
+impl Hash for LuaFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state)
+    }
+}
+
+impl Drop for LuaFunction {
+    fn drop(&mut self) {
+        lua::function::remove(self.lua_ref)
+    }
+}
+
+impl Poppable for LuaFunction {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        match ffi::lua_type(lstate, -1) {
+            ffi::LUA_TFUNCTION => {
+                let lua_ref = ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX);
+                Ok(Self { lua_ref })
+            },
+
+            ffi::LUA_TNONE => Err(lua::Error::PopEmptyStack),
+
+            other => Err(lua::Error::pop_wrong_type::<Self>(
+                ffi::LUA_TFUNCTION,
+                other,
+            )),
+        }
+    }
+}
+
+impl Pushable for LuaFunction {
+    unsafe fn push(
+        self,
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<c_int, lua::Error> {
+        ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+        Ok(1)
+    }
+}
+
+impl LuaFunction {
+    /// Creates a new registry reference to the function referenced by
+    /// `lua_ref`, leaving the ownership of `lua_ref` to the caller.
+    pub(crate) fn from_borrowed_ref(lua_ref: LuaRef) -> Self {
+        let lua_ref = unsafe {
+            lua::with_state(|lstate| {
+                ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, lua_ref);
+                ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX)
+            })
+        };
+        Self { lua_ref }
+    }
+
+    /// Returns the address of the referenced function, which identifies it
+    /// for as long as it's alive.
+    fn as_ptr(&self) -> *const c_void {
+        unsafe {
+            lua::with_state(|lstate| {
+                ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+                let ptr = ffi::lua_topointer(lstate, -1);
+                ffi::lua_pop(lstate, 1);
+                ptr
+            })
+        }
+    }
+
+    /// Calls the function with the given arguments, popping its return
+    /// value as an `R`.
+    pub fn call<A, R>(&self, args: A) -> Result<R, lua::Error>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        lua::function::call(self.lua_ref, args)
+    }
+}
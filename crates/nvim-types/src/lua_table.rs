@@ -0,0 +1,141 @@
+use std::ffi::{c_int, c_void};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use luajit_bindings::{self as lua, ffi, Poppable, Pushable};
+
+use crate::LuaRef;
+
+/// A Lua table stored in the Lua registry, e.g. the value of a Lua module.
+///
+/// Unlike a [`Dictionary`](crate::Dictionary), the table isn't converted to
+/// an [`Object`](crate::Object), so it can contain any Lua value, including
+/// functions and userdata. Its fields are read with [`get`](LuaTable::get),
+/// and the reference is removed from the registry when the `LuaTable` is
+/// dropped.
+///
+/// Two `LuaTable`s are equal if they refer to the same Lua table, even
+/// through different registry references.
+pub struct LuaTable {
+    lua_ref: LuaRef,
+}
+
+impl fmt::Debug for LuaTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<lua table {}>", self.lua_ref)
+    }
+}
+
+impl Clone for LuaTable {
+    fn clone(&self) -> Self {
+        let lua_ref = unsafe {
+            lua::with_state(|lstate| {
+                ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+                ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX)
+            })
+        };
+        Self { lua_ref }
+    }
+}
+
+impl PartialEq for LuaTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ptr() == other.as_ptr()
+    }
+}
+
+impl Eq for LuaTable {}
+
+impl Hash for LuaTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state)
+    }
+}
+
+impl Drop for LuaTable {
+    fn drop(&mut self) {
+        unsafe {
+            lua::with_state(|lstate| {
+                ffi::luaL_unref(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref)
+            })
+        }
+    }
+}
+
+impl Poppable for LuaTable {
+    unsafe fn pop(
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<Self, lua::Error> {
+        match ffi::lua_type(lstate, -1) {
+            ffi::LUA_TTABLE => {
+                let lua_ref = ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX);
+                Ok(Self { lua_ref })
+            },
+
+            ffi::LUA_TNONE => Err(lua::Error::PopEmptyStack),
+
+            other => {
+                Err(lua::Error::pop_wrong_type::<Self>(ffi::LUA_TTABLE, other))
+            },
+        }
+    }
+}
+
+impl Pushable for LuaTable {
+    unsafe fn push(
+        self,
+        lstate: *mut lua::ffi::lua_State,
+    ) -> Result<c_int, lua::Error> {
+        ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+        Ok(1)
+    }
+}
+
+impl LuaTable {
+    /// Returns the address of the referenced table, which identifies it for
+    /// as long as it's alive.
+    fn as_ptr(&self) -> *const c_void {
+        unsafe {
+            lua::with_state(|lstate| {
+                ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+                let ptr = ffi::lua_topointer(lstate, -1);
+                ffi::lua_pop(lstate, 1);
+                ptr
+            })
+        }
+    }
+
+    /// Returns the value of the table at `key`, popping it as a `V`. Like
+    /// `table[key]` in Lua this goes through the table's `__index`
+    /// metamethod, which is how e.g. the `vim` module lazily loads its
+    /// submodules, and an error raised by it is returned.
+    pub fn get<K, V>(&self, key: K) -> Result<V, lua::Error>
+    where
+        K: Pushable,
+        V: Poppable,
+    {
+        unsafe extern "C" fn index(lstate: *mut ffi::lua_State) -> c_int {
+            ffi::lua_gettable(lstate, 1);
+            1
+        }
+
+        unsafe {
+            lua::with_state(|lstate| {
+                let top = ffi::lua_gettop(lstate);
+
+                ffi::lua_pushcfunction(lstate, index);
+                ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, self.lua_ref);
+
+                let value = key
+                    .push(lstate)
+                    .and_then(|nkeys| lua::function::pcall(lstate, 1 + nkeys));
+
+                // Restores the stack if pushing the key or popping the value
+                // failed midway.
+                ffi::lua_settop(lstate, top);
+
+                value
+            })
+        }
+    }
+}
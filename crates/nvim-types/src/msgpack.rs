@@ -0,0 +1,222 @@
+//! Conversions between [`Object`]s and MessagePack, the wire format used by
+//! Neovim's RPC API.
+
+use rmp::encode;
+use rmpv::Value;
+use thiserror::Error as ThisError;
+
+use crate::{Array, Dictionary, Object, ObjectKind};
+
+// The `types` in the output of `nvim_get_api_info()`.
+const EXT_BUFFER: i8 = 0;
+const EXT_WINDOW: i8 = 1;
+const EXT_TABPAGE: i8 = 2;
+
+/// Error returned when decoding an [`Object`] from MessagePack.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error(transparent)]
+    Decode(#[from] rmpv::decode::Error),
+
+    #[error("Found {0} trailing bytes after the encoded object")]
+    TrailingBytes(usize),
+
+    #[error("Integer {0} doesn't fit in an i64")]
+    IntegerOverflow(u64),
+
+    #[error("Map keys must be strings, found {0}")]
+    NonStringKey(Value),
+
+    #[error("Unknown extension type {0}")]
+    UnknownExt(i8),
+
+    #[error("Couldn't decode handle from extension type {0}")]
+    BadHandle(i8),
+}
+
+impl Object {
+    /// Encodes the object as MessagePack, the same way Neovim does over RPC.
+    ///
+    /// Lua references can't be sent over the wire and are encoded as `nil`.
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_object(self, &mut buf);
+        buf
+    }
+
+    /// Decodes an object from MessagePack.
+    ///
+    /// The extension types Neovim uses for buffer, window and tabpage handles
+    /// are decoded as integers, which is how the handles are represented on
+    /// the Lua side.
+    pub fn from_msgpack(mut bytes: &[u8]) -> Result<Self, Error> {
+        let value = rmpv::decode::read_value(&mut bytes)?;
+
+        if !bytes.is_empty() {
+            return Err(Error::TrailingBytes(bytes.len()));
+        }
+
+        decode_value(value)
+    }
+}
+
+fn encode_object(obj: &Object, buf: &mut Vec<u8>) {
+    // Writing into a `Vec<u8>` can't fail.
+    match obj.kind() {
+        ObjectKind::Nil | ObjectKind::LuaRef => {
+            encode::write_nil(buf).unwrap();
+        },
+
+        ObjectKind::Boolean => {
+            let bool = unsafe { obj.as_boolean_unchecked() };
+            encode::write_bool(buf, bool).unwrap();
+        },
+
+        ObjectKind::Integer => {
+            let int = unsafe { obj.as_integer_unchecked() };
+            encode::write_sint(buf, int).unwrap();
+        },
+
+        ObjectKind::Float => {
+            let float = unsafe { obj.as_float_unchecked() };
+            encode::write_f64(buf, float).unwrap();
+        },
+
+        ObjectKind::String => {
+            encode_str(obj.as_string().unwrap().as_bytes(), buf)
+        },
+
+        ObjectKind::Array => {
+            let array = obj.as_array().unwrap();
+            encode::write_array_len(buf, array.len() as u32).unwrap();
+            for obj in array {
+                encode_object(obj, buf);
+            }
+        },
+
+        ObjectKind::Dictionary => {
+            let dict = obj.as_dict().unwrap();
+            encode::write_map_len(buf, dict.len() as u32).unwrap();
+            for pair in dict {
+                encode_str(pair.key().as_bytes(), buf);
+                encode_object(pair.value(), buf);
+            }
+        },
+    }
+}
+
+/// Neovim strings aren't guaranteed to be valid UTF-8, so the bytes are
+/// written as is.
+fn encode_str(bytes: &[u8], buf: &mut Vec<u8>) {
+    encode::write_str_len(buf, bytes.len() as u32).unwrap();
+    buf.extend_from_slice(bytes);
+}
+
+fn decode_value(value: Value) -> Result<Object, Error> {
+    let obj = match value {
+        Value::Nil => Object::nil(),
+
+        Value::Boolean(bool) => bool.into(),
+
+        Value::Integer(int) => match int.as_i64() {
+            Some(int) => int.into(),
+            None => return Err(Error::IntegerOverflow(int.as_u64().unwrap())),
+        },
+
+        Value::F32(float) => float.into(),
+
+        Value::F64(float) => float.into(),
+
+        Value::String(str) => {
+            crate::String::from_bytes(str.into_bytes()).into()
+        },
+
+        Value::Binary(bytes) => crate::String::from_bytes(bytes).into(),
+
+        Value::Array(values) => values
+            .into_iter()
+            .map(decode_value)
+            .collect::<Result<Array, _>>()?
+            .into(),
+
+        Value::Map(pairs) => pairs
+            .into_iter()
+            .map(|(key, value)| {
+                let key = match key {
+                    Value::String(str) => {
+                        crate::String::from_bytes(str.into_bytes())
+                    },
+                    Value::Binary(bytes) => crate::String::from_bytes(bytes),
+                    other => return Err(Error::NonStringKey(other)),
+                };
+                Ok((key, decode_value(value)?))
+            })
+            .collect::<Result<Dictionary, _>>()?
+            .into(),
+
+        Value::Ext(ty @ (EXT_BUFFER | EXT_WINDOW | EXT_TABPAGE), data) => {
+            let handle = rmp::decode::read_int::<i64, _>(&mut &*data)
+                .map_err(|_| Error::BadHandle(ty))?;
+            handle.into()
+        },
+
+        Value::Ext(ty, _) => return Err(Error::UnknownExt(ty)),
+    };
+
+    Ok(obj)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_nested() {
+        let obj = Object::from(Dictionary::from_iter([
+            ("nil", Object::nil()),
+            ("bool", true.into()),
+            ("int", (-42i64).into()),
+            ("float", 1.5f64.into()),
+            ("str", "foo".into()),
+            (
+                "array",
+                Array::from_iter([
+                    Object::from(1i64),
+                    "bar".into(),
+                    Dictionary::from_iter([("baz", false)]).into(),
+                ])
+                .into(),
+            ),
+        ]));
+
+        let bytes = obj.to_msgpack();
+        assert_eq!(obj, Object::from_msgpack(&bytes).unwrap());
+    }
+
+    #[test]
+    fn handles_as_integers() {
+        // A truncated handle.
+        let bytes = [0xd4, EXT_WINDOW as u8, 0xcd];
+        assert!(matches!(
+            Object::from_msgpack(&bytes),
+            Err(Error::BadHandle(EXT_WINDOW))
+        ));
+
+        // A window handle with id 1000, encoded as Neovim does.
+        let bytes = [0xc7, 0x03, EXT_WINDOW as u8, 0xcd, 0x03, 0xe8];
+        assert_eq!(
+            Object::from(1000i64),
+            Object::from_msgpack(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let mut bytes = Object::from(true).to_msgpack();
+        bytes.push(0xc0);
+        assert!(matches!(
+            Object::from_msgpack(&bytes),
+            Err(Error::TrailingBytes(1))
+        ));
+    }
+}
@@ -75,7 +75,30 @@ impl Default for Object {
 
 impl fmt::Debug for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, f)
+        use ObjectKind::*;
+        let (variant, field): (_, &dyn fmt::Debug) = match self.ty {
+            Nil => return f.write_str("Nil"),
+            Boolean => ("Boolean", unsafe { &self.data.boolean }),
+            Integer => ("Integer", unsafe { &self.data.integer }),
+            Float => ("Float", unsafe { &self.data.float }),
+            String => {
+                ("String", &unsafe { self.data.string.to_string_lossy() })
+            },
+            Array => ("Array", unsafe { &*self.data.array }),
+            Dictionary => ("Dictionary", unsafe { &*self.data.dictionary }),
+            LuaRef => ("LuaRef", unsafe { &self.data.luaref }),
+        };
+        f.debug_tuple(variant).field(field).finish()
+    }
+}
+
+/// Formats a value with its `Display` implementation when passed to the
+/// `Debug` builders of `fmt::Formatter`.
+pub(crate) struct DisplayAsDebug<'a, T>(pub(crate) &'a T);
+
+impl<T: fmt::Display> fmt::Debug for DisplayAsDebug<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
     }
 }
 
@@ -88,8 +111,12 @@ impl fmt::Display for Object {
             Integer => write!(f, "{}", unsafe { self.data.integer }),
             Float => write!(f, "{}", unsafe { self.data.float }),
             String => write!(f, "\"{}\"", unsafe { &*self.data.string }),
-            Array => write!(f, "{}", unsafe { &*self.data.array }),
-            Dictionary => write!(f, "{}", unsafe { &*self.data.dictionary }),
+            // Forward the formatter to preserve flags like `{:#}`, which
+            // pretty-prints nested arrays and dictionaries.
+            Array => fmt::Display::fmt(unsafe { &*self.data.array }, f),
+            Dictionary => {
+                fmt::Display::fmt(unsafe { &*self.data.dictionary }, f)
+            },
             LuaRef => write!(f, "LuaRef({})", unsafe { self.data.luaref }),
         }
     }
@@ -132,6 +159,106 @@ impl Object {
         unsafe { NonOwning::new(std::ptr::read(self)) }
     }
 
+    /// Returns a reference to the inner [`String`](crate::String) if the
+    /// object is a string, or `None` otherwise.
+    #[inline]
+    pub fn as_string(&self) -> Option<&crate::String> {
+        matches!(self.ty, ObjectKind::String)
+            .then(|| unsafe { &*self.data.string })
+    }
+
+    /// Returns a reference to the inner [`Array`] if the object is an array,
+    /// or `None` otherwise.
+    #[inline]
+    pub fn as_array(&self) -> Option<&Array> {
+        matches!(self.ty, ObjectKind::Array)
+            .then(|| unsafe { &*self.data.array })
+    }
+
+    /// Returns a reference to the inner [`Dictionary`] if the object is a
+    /// dictionary, or `None` otherwise.
+    #[inline]
+    pub fn as_dict(&self) -> Option<&Dictionary> {
+        matches!(self.ty, ObjectKind::Dictionary)
+            .then(|| unsafe { &*self.data.dictionary })
+    }
+
+    /// Returns a JSON-like representation of the object, with the elements of
+    /// non-empty arrays and dictionaries on their own lines, indented by
+    /// `indent` spaces per nesting level. Useful to log nested objects.
+    ///
+    /// Nil is written as `null`, strings and dictionary keys are quoted and
+    /// escaped, and Lua references are written as `LuaRef(n)`.
+    pub fn to_debug_string(&self, indent: usize) -> std::string::String {
+        let mut out = std::string::String::new();
+        self.write_debug_string(&mut out, indent, 0);
+        out
+    }
+
+    fn write_debug_string(
+        &self,
+        out: &mut std::string::String,
+        indent: usize,
+        level: usize,
+    ) {
+        use std::fmt::Write;
+
+        let pad = |out: &mut std::string::String, level: usize| {
+            out.push_str(&" ".repeat(indent * level))
+        };
+
+        // Writing into a `String` can't fail.
+        match self.ty {
+            ObjectKind::Nil => out.push_str("null"),
+
+            ObjectKind::String => {
+                let str = unsafe { self.data.string.to_string_lossy() };
+                write!(out, "{str:?}").unwrap();
+            },
+
+            ObjectKind::Array => {
+                let array = unsafe { &*self.data.array };
+                if array.is_empty() {
+                    return out.push_str("[]");
+                }
+                out.push_str("[\n");
+                for (idx, obj) in array.iter().enumerate() {
+                    pad(out, level + 1);
+                    obj.write_debug_string(out, indent, level + 1);
+                    if idx + 1 < array.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                pad(out, level);
+                out.push(']');
+            },
+
+            ObjectKind::Dictionary => {
+                let dict = unsafe { &*self.data.dictionary };
+                if dict.is_empty() {
+                    return out.push_str("{}");
+                }
+                out.push_str("{\n");
+                for (idx, pair) in dict.iter().enumerate() {
+                    pad(out, level + 1);
+                    write!(out, "{:?}: ", pair.key().to_string_lossy())
+                        .unwrap();
+                    pair.value().write_debug_string(out, indent, level + 1);
+                    if idx + 1 < dict.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                pad(out, level);
+                out.push('}');
+            },
+
+            // Booleans, numbers and Lua references are displayed as usual.
+            _ => write!(out, "{self}").unwrap(),
+        }
+    }
+
     /// TODO: docs
     #[inline(always)]
     pub unsafe fn as_boolean_unchecked(&self) -> bool {
@@ -486,45 +613,101 @@ mod tests {
         assert_eq!(str, str_again.unwrap());
     }
 
+    #[test]
+    fn as_array() {
+        let obj = Object::from(Array::from((1, "foo")));
+        assert_eq!(Some(&Array::from((1, "foo"))), obj.as_array());
+        assert_eq!(None, obj.as_dict());
+    }
+
+    #[test]
+    fn as_dict_entries() {
+        let obj = Object::from(Dictionary::from_iter([
+            ("foo", Object::from("bar")),
+            ("baz", Object::from(19)),
+        ]));
+        assert_eq!(None, obj.as_array());
+
+        let entries = obj.as_dict().unwrap().entries().collect::<Vec<_>>();
+        assert_eq!(
+            vec![("foo", &Object::from("bar")), ("baz", &Object::from(19))],
+            entries
+        );
+    }
+
     #[test]
     fn print_nil() {
         let obj = Object::nil();
-        assert_eq!("()", &format!("{obj:?}"));
+        assert_eq!("Nil", &format!("{obj:?}"));
         assert_eq!("()", &format!("{obj}"));
     }
 
     #[test]
     fn print_boolean() {
         let obj = Object::from(true);
-        assert_eq!("true", &format!("{obj:?}"));
+        assert_eq!("Boolean(true)", &format!("{obj:?}"));
         assert_eq!("true", &format!("{obj}"));
     }
 
     #[test]
     fn print_integer() {
         let obj = Object::from(42);
-        assert_eq!("42", &format!("{obj:?}"));
+        assert_eq!("Integer(42)", &format!("{obj:?}"));
         assert_eq!("42", &format!("{obj}"));
     }
 
     #[test]
     fn print_float() {
         let obj = Object::from(42.1);
-        assert_eq!("42.1", &format!("{obj:?}"));
+        assert_eq!("Float(42.1)", &format!("{obj:?}"));
         assert_eq!("42.1", &format!("{obj}"));
     }
 
     #[test]
     fn print_string() {
         let obj = Object::from("foobar");
-        assert_eq!("\"foobar\"", &format!("{obj:?}"));
+        assert_eq!("String(\"foobar\")", &format!("{obj:?}"));
         assert_eq!("\"foobar\"", &format!("{obj}"));
     }
 
+    #[test]
+    fn to_debug_string() {
+        let obj = Object::from(Dictionary::from_iter([
+            ("foo", Object::from(Array::from((1, "a\"b", false)))),
+            ("bar", Object::from(Dictionary::new())),
+            ("baz", Object::from(Array::new())),
+            ("qux", Object::from(Dictionary::from_iter([("x", 1.5)]))),
+        ]));
+
+        let expected = r#"{
+  "foo": [
+    1,
+    "a\"b",
+    false
+  ],
+  "bar": {},
+  "baz": [],
+  "qux": {
+    "x": 1.5
+  }
+}"#;
+        assert_eq!(expected, obj.to_debug_string(2));
+
+        assert_eq!(
+            "[\ntrue\n]",
+            Object::from(Array::from((true,))).to_debug_string(0)
+        );
+        assert_eq!("42", Object::from(42).to_debug_string(4));
+        assert_eq!("null", Object::nil().to_debug_string(4));
+    }
+
     #[test]
     fn print_array() {
         let obj = Object::from(Array::from((42.1, true, "foo")));
-        assert_eq!("[42.1, true, \"foo\"]", &format!("{obj:?}"));
+        assert_eq!(
+            "Array([Float(42.1), Boolean(true), String(\"foo\")])",
+            &format!("{obj:?}")
+        );
         assert_eq!("[42.1, true, \"foo\"]", &format!("{obj}"));
     }
 
@@ -534,10 +717,27 @@ mod tests {
             ("foo", Object::from("bar")),
             ("baz", Object::from(19)),
         ]));
-        assert_eq!("{foo: \"bar\", baz: 19}", &format!("{obj:?}"));
+        assert_eq!(
+            "Dictionary({foo: String(\"bar\"), baz: Integer(19)})",
+            &format!("{obj:?}")
+        );
         assert_eq!("{foo: \"bar\", baz: 19}", &format!("{obj}"));
     }
 
+    #[test]
+    fn print_pretty() {
+        let obj = Object::from(Array::from((
+            1,
+            "foo",
+            Dictionary::from_iter([("bar", Array::from((true,)))]),
+        )));
+        assert_eq!(
+            "[\n    1,\n    \"foo\",\n    {\n        bar: [\n            \
+             true,\n        ],\n    },\n]",
+            &format!("{obj:#}")
+        );
+    }
+
     #[test]
     fn print_luaref() {
         let obj = Object::from_luaref(42);
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::ffi::{c_char, c_int, OsStr};
+use std::ffi::{c_char, c_int, OsStr, OsString};
 use std::mem::ManuallyDrop;
 use std::path::PathBuf;
 use std::string::{self, String as StdString};
@@ -80,6 +80,29 @@ impl String {
         }
     }
 
+    /// Returns `true` if the `String` starts with the `prefix` bytes.
+    #[inline]
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.as_bytes().starts_with(prefix)
+    }
+
+    /// Returns `true` if the `String` ends with the `suffix` bytes.
+    #[inline]
+    pub fn ends_with(&self, suffix: &[u8]) -> bool {
+        self.as_bytes().ends_with(suffix)
+    }
+
+    /// Returns `true` if the `String` contains the `needle` bytes as a
+    /// contiguous subsequence. An empty `needle` is always contained.
+    #[inline]
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        needle.is_empty()
+            || self
+                .as_bytes()
+                .windows(needle.len())
+                .any(|window| window == needle)
+    }
+
     /// Returns a string slice of this `String`'s contents. Fails if it doesn't
     /// contain a valid UTF-8 byte sequence.
     #[inline]
@@ -199,7 +222,28 @@ impl From<Vec<u8>> for String {
 impl From<PathBuf> for String {
     #[inline]
     fn from(path: PathBuf) -> Self {
-        path.display().to_string().into()
+        path.into_os_string().into()
+    }
+}
+
+/// On Unix OS strings are arbitrary byte sequences, which are passed to
+/// Neovim as they are.
+#[cfg(not(windows))]
+impl From<OsString> for String {
+    #[inline]
+    fn from(os_string: OsString) -> Self {
+        use std::os::unix::ffi::OsStringExt;
+        Self::from_bytes(os_string.into_vec())
+    }
+}
+
+/// On Windows OS strings can contain unpaired surrogates which can't be
+/// encoded in UTF-8, so they're replaced with `�`.
+#[cfg(windows)]
+impl From<OsString> for String {
+    #[inline]
+    fn from(os_string: OsString) -> Self {
+        os_string.to_string_lossy().into()
     }
 }
 
@@ -312,4 +356,30 @@ mod tests {
         let bytes = s.into_bytes();
         assert_eq!(&[104, 101, 108, 108, 111][..], &bytes[..]);
     }
+
+    #[test]
+    fn byte_predicates() {
+        let path = String::from_bytes(b"src/\xffmain.rs".to_vec());
+        assert!(path.as_str().is_err());
+
+        assert!(path.starts_with(b"src/"));
+        assert!(path.ends_with(b".rs"));
+        assert!(!path.ends_with(b".lua"));
+        assert!(path.contains(b"main"));
+        assert!(path.contains(b""));
+        assert!(!path.contains(b"lib"));
+        assert!(!String::from("").contains(b"a"));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn from_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = PathBuf::from(OsStr::from_bytes(b"foo/\xffbar.rs"));
+
+        let nstr = String::from(path.clone());
+        assert_eq!(b"foo/\xffbar.rs", nstr.as_bytes());
+        assert_eq!(path, PathBuf::from(nstr));
+    }
 }
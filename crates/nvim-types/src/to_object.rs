@@ -103,6 +103,37 @@ impl<T: ToObject> ToObject for Vec<T> {
     }
 }
 
+/// Implements `ToObject` for tuples, which are converted to arrays, with the
+/// same arities as `From<(..)> for Array`.
+macro_rules! impl_tuple {
+    ($($ty:ident)*) => {
+        impl <$($ty: ToObject),*> ToObject for ($($ty,)*) {
+            #[allow(non_snake_case)]
+            fn to_obj(self) -> Result<Object, Error> {
+                let ($($ty,)*) = self;
+                Ok(Array::from_iter([$($ty.to_obj()?,)*]).into())
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A B);
+impl_tuple!(A B C);
+impl_tuple!(A B C D);
+impl_tuple!(A B C D E);
+impl_tuple!(A B C D E F);
+impl_tuple!(A B C D E F G);
+impl_tuple!(A B C D E F G H);
+impl_tuple!(A B C D E F G H I);
+impl_tuple!(A B C D E F G H I J);
+impl_tuple!(A B C D E F G H I J K);
+impl_tuple!(A B C D E F G H I J K L);
+impl_tuple!(A B C D E F G H I J K L M);
+impl_tuple!(A B C D E F G H I J K L M N);
+impl_tuple!(A B C D E F G H I J K L M N O);
+impl_tuple!(A B C D E F G H I J K L M N O P);
+
 impl<K, V> ToObject for HashMap<K, V>
 where
     K: Into<crate::String>,
@@ -115,3 +146,12 @@ where
             .map(Into::into)
     }
 }
+
+#[cfg(feature = "serde_json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl ToObject for serde_json::Value {
+    fn to_obj(self) -> Result<Object, Error> {
+        use serde::Serialize;
+        self.serialize(crate::Serializer::new()).map_err(Into::into)
+    }
+}
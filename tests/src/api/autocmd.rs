@@ -1,6 +1,9 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use all_asserts::*;
 use nvim_oxi as oxi;
-use nvim_oxi::api::{self, opts::*, Buffer};
+use nvim_oxi::api::{self, opts::*, types::*, Buffer};
 
 #[oxi::test]
 fn clear_autocmds_current_buf() {
@@ -79,9 +82,6 @@ fn create_autocmd_buffer_n_patterns() {
 
 #[oxi::test]
 fn exec_autocmds() {
-    use std::cell::RefCell;
-    use std::rc::Rc;
-
     let i = Rc::new(RefCell::new(0));
 
     let cloned = Rc::clone(&i);
@@ -99,7 +99,7 @@ fn exec_autocmds() {
     let id = api::create_autocmd(["BufAdd"], &opts);
     assert!(id.is_ok(), "{id:?}");
 
-    let opts = ExecAutocmdsOpts::builder().buffer(0).build();
+    let opts = ExecAutocmdsOpts::builder().buffer(0).build().unwrap();
 
     let res = api::exec_autocmds(["BufAdd"], &opts);
     assert_eq!(Ok(()), res);
@@ -111,6 +111,72 @@ fn exec_autocmds() {
     assert_eq!(1, *i.try_borrow().unwrap());
 }
 
+#[cfg(not(feature = "neovim-0-7"))]
+#[oxi::test]
+fn exec_autocmds_typed_data() {
+    use oxi::{Dictionary, FromObject, Object, ToObject};
+
+    #[derive(Debug, PartialEq)]
+    struct Payload {
+        name: String,
+        count: i64,
+    }
+
+    impl ToObject for Payload {
+        fn to_obj(self) -> Result<Object, oxi::ToObjectError> {
+            let dict = Dictionary::from_iter([
+                ("name", Object::from(self.name)),
+                ("count", Object::from(self.count)),
+            ]);
+            Ok(dict.into())
+        }
+    }
+
+    impl FromObject for Payload {
+        fn from_obj(obj: Object) -> Result<Self, oxi::FromObjectError> {
+            let dict = Dictionary::from_obj(obj)?;
+            let get = |key| dict.get(&key).cloned().unwrap_or_default();
+            Ok(Self {
+                name: String::from_obj(get("name"))?,
+                count: i64::from_obj(get("count"))?,
+            })
+        }
+    }
+
+    let received = Rc::new(RefCell::new(None));
+    let cloned = Rc::clone(&received);
+
+    let opts = CreateAutocmdOpts::builder()
+        .callback(move |args: api::types::AutocmdCallbackArgs| {
+            *cloned.borrow_mut() = Some(Payload::from_obj(args.data)?);
+            Ok::<_, oxi::Error>(false)
+        })
+        .patterns(["TypedData"])
+        .once(true)
+        .build();
+
+    api::create_autocmd(["User"], &opts).unwrap();
+
+    let opts = ExecAutocmdsOpts::builder()
+        .patterns("TypedData")
+        .data(Payload { name: "foo".into(), count: 42 })
+        .build()
+        .unwrap();
+
+    assert_eq!(Ok(()), api::exec_autocmds(["User"], &opts));
+    assert_eq!(
+        Some(Payload { name: "foo".into(), count: 42 }),
+        received.take()
+    );
+}
+
+#[cfg(not(feature = "neovim-0-7"))]
+#[oxi::test]
+fn exec_autocmds_data_conversion_error() {
+    let opts = ExecAutocmdsOpts::builder().data(u64::MAX).build();
+    assert!(opts.is_err());
+}
+
 #[oxi::test]
 fn get_autocmds() {
     let autocmds =
@@ -118,6 +184,30 @@ fn get_autocmds() {
     assert_lt!(0, autocmds.collect::<Vec<_>>().len());
 }
 
+#[oxi::test]
+fn on_mode_change() {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+
+    let id = api::on_mode_change({
+        let changes = Rc::clone(&changes);
+        move |args| {
+            changes.borrow_mut().push((args.old, args.new));
+            Ok(())
+        }
+    })
+    .unwrap();
+
+    let keys = api::replace_termcodes("i<Esc>", true, false, true);
+    api::feedkeys_and_wait(keys, FeedkeysMode::NoRemap).unwrap();
+
+    assert_eq!(
+        vec![(Mode::Normal, Mode::Insert), (Mode::Insert, Mode::Normal)],
+        *changes.borrow()
+    );
+
+    api::del_autocmd(id).unwrap();
+}
+
 #[oxi::test]
 fn set_del_augroup_by_id() {
     let id = api::create_augroup("Foo", &Default::default())
@@ -141,7 +231,7 @@ fn set_exec_del_autocmd() {
     let id = api::create_autocmd(["BufAdd, BufDelete"], &opts)
         .expect("create_autocmd failed");
 
-    let opts = ExecAutocmdsOpts::builder().build();
+    let opts = ExecAutocmdsOpts::builder().build().unwrap();
     assert_eq!(Ok(()), api::exec_autocmds(["BufAdd"], &opts));
 
     assert_eq!(Ok(()), api::del_autocmd(id));
@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use all_asserts::*;
 use nvim_oxi as oxi;
 use nvim_oxi::api::{self, opts::*, types::*, Buffer};
@@ -21,6 +24,67 @@ fn attach() {
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
 }
 
+#[oxi::test]
+fn attach_guard_detaches() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    let count = Rc::new(RefCell::new(0));
+
+    let opts = BufAttachOpts::builder()
+        .on_lines({
+            let count = Rc::clone(&count);
+            move |_args| {
+                *count.borrow_mut() += 1;
+                Ok(false)
+            }
+        })
+        .build();
+
+    let guard = buf.attach_guarded(false, &opts).unwrap();
+    assert_eq!(&buf, guard.buffer());
+
+    buf.set_lines(0, 1, true, ["foo"]).unwrap();
+    assert_eq!(1, *count.borrow());
+
+    drop(guard);
+
+    buf.set_lines(0, 1, true, ["bar"]).unwrap();
+    buf.set_lines(0, 1, true, ["baz"]).unwrap();
+    assert_eq!(1, *count.borrow());
+}
+
+#[oxi::test]
+fn on_wipe() {
+    let buf = api::create_buf(true, false).unwrap();
+    let wiped = Rc::new(RefCell::new(false));
+    let detached = Rc::new(RefCell::new(false));
+
+    let opts = BufAttachOpts::builder()
+        .on_detach({
+            let detached = Rc::clone(&detached);
+            move |_args| {
+                *detached.borrow_mut() = true;
+                Ok(false)
+            }
+        })
+        .build();
+
+    buf.attach(false, &opts).unwrap();
+
+    let res = buf.on_wipe({
+        let wiped = Rc::clone(&wiped);
+        move || {
+            *wiped.borrow_mut() = true;
+            Ok(())
+        }
+    });
+    assert!(res.is_ok(), "{res:?}");
+    assert!(!*wiped.borrow());
+
+    buf.clone().delete(&Default::default()).unwrap();
+    assert!(*wiped.borrow());
+    assert!(*detached.borrow());
+}
+
 #[oxi::test]
 fn buf_call() {
     let buf = Buffer::current();
@@ -132,6 +196,237 @@ fn set_get_del_lines() {
     assert_eq!(Ok(1), buf.line_count());
 }
 
+#[oxi::test]
+fn set_get_lines_from_end() {
+    let mut buf = Buffer::current();
+
+    buf.set_lines(0, Index::FromEnd(0), true, ["a", "b", "c", "d"]).unwrap();
+    assert_eq!(Ok(4), buf.line_count());
+
+    buf.set_lines(2, Index::FromEnd(0), true, ["foo"]).unwrap();
+    assert_eq!(
+        vec!["a", "b", "foo"],
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    assert_eq!(
+        vec!["foo"],
+        buf.get_lines(Index::FromEnd(1), Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[oxi::test]
+fn get_lines_empty_buffer() {
+    let buf = api::create_buf(true, false).unwrap();
+
+    assert_eq!(Ok(1), buf.line_count());
+    assert_eq!(0, buf.get_lines(0, 0, true).unwrap().count());
+    assert_eq!(
+        vec![""],
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[oxi::test]
+fn get_set_lines_inverted_range() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, Index::FromEnd(0), true, ["a", "b", "c"]).unwrap();
+
+    let err = buf.get_lines(2, 1, false).err().map(|err| err.to_string());
+    assert_eq!(Some("start 2 > end 1".into()), err);
+
+    let err = buf
+        .get_lines(Index::FromEnd(1), Index::FromEnd(2), false)
+        .err()
+        .map(|err| err.to_string());
+    assert_eq!(Some("start -2 > end -3".into()), err);
+
+    let err = buf.set_lines(2, 1, false, ["foo"]).unwrap_err();
+    assert_eq!("start 2 > end 1", err.to_string());
+    assert_eq!(Ok(3), buf.line_count());
+}
+
+#[oxi::test]
+fn get_numbered_lines() {
+    let mut buf = Buffer::current();
+    let lines = (1..=20).map(|n| format!("line {n}")).collect::<Vec<_>>();
+    buf.set_lines(0, Index::FromEnd(0), true, lines).unwrap();
+
+    let mut lines = buf.get_numbered_lines(5..10).unwrap();
+    assert_eq!(5, lines.len());
+    assert_eq!(Some((6, "line 6".into())), lines.next());
+    assert_eq!(Some((10, "line 10".into())), lines.next_back());
+
+    let lines = buf.get_numbered_lines(18..).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec![(19, "line 19".into()), (20, "line 20".into())], lines);
+
+    let lines = buf.get_numbered_lines(..=0).unwrap().collect::<Vec<_>>();
+    assert_eq!(vec![(1, "line 1".into())], lines);
+
+    let lines = buf.get_numbered_lines(..=usize::MAX).unwrap();
+    assert_eq!(20, lines.len());
+
+    let lines = buf.get_numbered_lines(usize::MAX..).unwrap();
+    assert_eq!(0, lines.len());
+}
+
+#[oxi::test]
+fn set_lines_append_at_eof() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(0, 1, true, ["foo"]).unwrap();
+
+    let count = buf.line_count().unwrap();
+    buf.set_lines(count, count, true, ["bar"]).unwrap();
+
+    assert_eq!(
+        vec!["foo", "bar"],
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[oxi::test]
+fn set_lines_replace_only_line() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(0, 1, true, ["foo"]).unwrap();
+
+    assert_eq!(Ok(1), buf.line_count());
+    assert_eq!(
+        vec!["foo"],
+        buf.get_lines(0, 1, true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+}
+
+#[oxi::test]
+fn set_lines_diff() {
+    let mut buf = api::create_buf(true, false).unwrap();
+
+    let mut lines =
+        (0..100).map(|idx| format!("line {idx}")).collect::<Vec<_>>();
+    buf.set_lines(0, Index::FromEnd(0), true, lines.iter().map(|l| &**l))
+        .unwrap();
+
+    let ns_id = api::create_namespace("set_lines_diff");
+    let opts = SetExtmarkOpts::default();
+    let ids = (0..100)
+        .map(|row| buf.set_extmark(ns_id, row, 2, &opts).unwrap())
+        .collect::<Vec<_>>();
+
+    lines[42] = "changed".into();
+    let new_lines = lines.iter().map(|l| &**l).collect::<Vec<_>>();
+    buf.set_lines_diff(&new_lines).unwrap();
+
+    assert_eq!(
+        lines,
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    let opts = GetExtmarkByIdOpts::default();
+    for (row, &id) in ids.iter().enumerate().filter(|&(row, _)| row != 42) {
+        let (mark_row, mark_col, _) =
+            buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+        assert_eq!((row, 2), (mark_row, mark_col));
+    }
+}
+
+#[oxi::test]
+fn set_lines_diff_edit_and_insertion() {
+    let mut buf = api::create_buf(true, false).unwrap();
+
+    let mut lines =
+        (0..100).map(|idx| format!("line {idx}")).collect::<Vec<_>>();
+    buf.set_lines(0, Index::FromEnd(0), true, lines.iter().map(|l| &**l))
+        .unwrap();
+
+    let ns_id = api::create_namespace("set_lines_diff_edit_and_insertion");
+    let opts = SetExtmarkOpts::default();
+    let ids = (0..100)
+        .map(|row| buf.set_extmark(ns_id, row, 2, &opts).unwrap())
+        .collect::<Vec<_>>();
+
+    // Changes the line count, with unchanged lines between the two edits.
+    lines[10] = "changed".into();
+    lines.insert(90, "inserted".into());
+    let new_lines = lines.iter().map(|l| &**l).collect::<Vec<_>>();
+    buf.set_lines_diff(&new_lines).unwrap();
+
+    assert_eq!(
+        lines,
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    );
+
+    // Every extmark except the one on the edited line is still on its line,
+    // shifted down by one after the insertion.
+    let opts = GetExtmarkByIdOpts::default();
+    for (row, &id) in ids.iter().enumerate().filter(|&(row, _)| row != 10) {
+        let (mark_row, mark_col, _) =
+            buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+        let expected = if row < 90 { row } else { row + 1 };
+        assert_eq!((expected, 2), (mark_row, mark_col), "line {row}");
+    }
+}
+
+#[oxi::test]
+fn read_lines_into() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, Index::FromEnd(0), true, ["foo", "bar", "baz"]).unwrap();
+
+    let mut lines = Vec::with_capacity(8);
+    let capacity = lines.capacity();
+
+    for _ in 0..3 {
+        buf.read_lines_into(0, Index::FromEnd(0), &mut lines).unwrap();
+        assert_eq!(vec!["foo", "bar", "baz"], lines);
+        assert_eq!(capacity, lines.capacity());
+    }
+
+    buf.read_lines_into(1, 2, &mut lines).unwrap();
+    assert_eq!(vec!["bar"], lines);
+    assert_eq!(capacity, lines.capacity());
+}
+
+#[oxi::test]
+fn prompt_buffer() {
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_buftype(BufType::Prompt).unwrap();
+    buf.set_prompt("> ").unwrap();
+
+    let submitted = Rc::new(RefCell::new(Vec::new()));
+
+    let lines = Rc::clone(&submitted);
+    buf.set_prompt_callback(move |line| {
+        lines.borrow_mut().push(line);
+        Ok(())
+    })
+    .unwrap();
+
+    api::set_current_buf(&buf).unwrap();
+    api::command("normal! ifoo\r").unwrap();
+    api::command("normal! ibar\r").unwrap();
+
+    assert_eq!(vec!["foo", "bar"], *submitted.borrow());
+}
+
 #[oxi::test]
 fn buf_set_get_del_mark() {
     let mut buf = Buffer::current();
@@ -142,7 +437,58 @@ fn buf_set_get_del_mark() {
     assert_eq!((1, 0), buf.get_mark('a').unwrap());
 
     let res = buf.del_mark('a');
-    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(true), res);
+
+    let res = buf.del_mark('a');
+    assert_eq!(Ok(false), res);
+}
+
+#[oxi::test]
+fn apply_edits() {
+    let lines = |buf: &Buffer| {
+        buf.get_lines(0, Index::FromEnd(0), true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    };
+
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(0, 1, true, ["foo bar baz"]).unwrap();
+
+    let mut expected = api::create_buf(true, false).unwrap();
+    expected.set_lines(0, 1, true, ["foo bar baz"]).unwrap();
+    expected.set_text(0, 8, 0, 11, ["zap"]).unwrap();
+    expected.set_text(0, 0, 0, 3, ["qux", "quux"]).unwrap();
+
+    let edits = [
+        TextEdit { start: (0, 0), end: (0, 3), new_text: "qux\nquux".into() },
+        TextEdit { start: (0, 8), end: (0, 11), new_text: "zap".into() },
+    ];
+    assert_eq!(Ok(()), buf.apply_edits(&edits));
+    assert_eq!(lines(&expected), lines(&buf));
+    assert_eq!(vec!["qux", "quux bar zap"], lines(&buf));
+
+    let overlapping = [
+        TextEdit { start: (0, 0), end: (0, 5), new_text: "a".into() },
+        TextEdit { start: (0, 4), end: (0, 6), new_text: "b".into() },
+    ];
+    assert!(buf.apply_edits(&overlapping).is_err());
+    assert_eq!(vec!["qux", "quux bar zap"], lines(&buf));
+}
+
+#[oxi::test]
+fn get_text_inverted_range() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, Index::FromEnd(0), true, ["foo", "bar"]).unwrap();
+
+    let err = buf
+        .get_text(1, 0, 0, 3, &Default::default())
+        .err()
+        .map(|err| err.to_string());
+    assert_eq!(Some("start (1, 0) > end (0, 3)".into()), err);
+
+    let err = buf.get_text(0, 2, 0, 1, &Default::default()).err();
+    assert!(err.is_some());
 }
 
 #[oxi::test]
@@ -208,3 +554,100 @@ fn buf_set_get_option() {
     buf.set_option("modified", false).unwrap();
     assert!(!buf.get_option::<bool>("modified").unwrap());
 }
+
+#[oxi::test]
+fn buf_option_setters() {
+    let mut buf = api::create_buf(false, true).unwrap();
+
+    buf.set_filetype("rust").unwrap();
+    assert_eq!("rust", buf.get_option::<String>("filetype").unwrap());
+
+    buf.set_buftype(BufType::Prompt).unwrap();
+    assert_eq!("prompt", buf.get_option::<String>("buftype").unwrap());
+
+    buf.set_buftype(BufType::Nofile).unwrap();
+    assert_eq!("nofile", buf.get_option::<String>("buftype").unwrap());
+
+    buf.set_modifiable(false).unwrap();
+    assert!(!buf.get_option::<bool>("modifiable").unwrap());
+    assert!(buf.set_lines(0, 1, true, ["foo"]).is_err());
+}
+
+#[oxi::test]
+fn leaked_scratch_buffers() {
+    let buf = api::create_buf(false, true).unwrap();
+    assert!(api::leaked_scratch_buffers().contains(&buf));
+
+    let listed = api::create_buf(true, false).unwrap();
+    assert!(!api::leaked_scratch_buffers().contains(&listed));
+
+    assert_eq!(Ok(vec![buf.clone()]), api::report_leaked_scratch_buffers());
+
+    buf.delete(&Default::default()).unwrap();
+    assert!(api::leaked_scratch_buffers().is_empty());
+}
+
+#[oxi::test]
+fn leaked_scratch_buffers_reported_on_exit() {
+    let _buf = api::create_buf(false, true).unwrap();
+
+    let opts = ExecAutocmdsOpts::builder().build().unwrap();
+    api::exec_autocmds(["VimLeavePre"], &opts).unwrap();
+
+    let messages = api::exec("messages", true).unwrap().unwrap_or_default();
+    assert!(messages.contains("never deleted"), "{messages}");
+}
+
+#[oxi::test]
+fn scratch_buffer() {
+    let buf = api::ScratchBuffer::builder()
+        .bufhidden("wipe")
+        .filetype("markdown")
+        .build()
+        .unwrap();
+
+    assert_eq!(Ok("nofile".into()), buf.get_option::<String>("buftype"));
+    assert_eq!(Ok("wipe".into()), buf.get_option::<String>("bufhidden"));
+    assert_eq!(Ok(false), buf.get_option("swapfile"));
+    assert_eq!(Ok("markdown".into()), buf.get_option::<String>("filetype"));
+    assert_eq!(Ok(false), buf.get_option("buflisted"));
+
+    // The buffer isn't displayed, so it's not wiped right away.
+    assert!(buf.is_valid());
+    buf.delete(&Default::default()).unwrap();
+}
+
+#[oxi::test]
+fn buffer_diagnostics() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(0, 1, true, ["foo", "bar", "baz"]).unwrap();
+
+    let ns = api::create_namespace("buffer_diagnostics");
+    let res = api::exec_lua_object(
+        "local buf, ns = ...
+        vim.diagnostic.set(ns, buf, {{
+          lnum = 1,
+          col = 2,
+          message = 'oops',
+          severity = vim.diagnostic.severity.WARN,
+          source = 'test',
+        }})",
+        oxi::Array::from_iter([
+            oxi::Object::from(buf.clone()),
+            oxi::Object::from(ns),
+        ]),
+    );
+    assert!(res.is_ok(), "{res:?}");
+
+    let diagnostics = buf.diagnostics(..).unwrap();
+    assert_eq!(1, diagnostics.len());
+
+    let diagnostic = &diagnostics[0];
+    assert_eq!(1, diagnostic.line);
+    assert_eq!(2, diagnostic.col);
+    assert_eq!(DiagnosticSeverity::Warn, diagnostic.severity);
+    assert_eq!("oops", diagnostic.message);
+    assert_eq!(Some("test"), diagnostic.source.as_deref());
+
+    assert_eq!(Ok(Vec::new()), buf.diagnostics(2..));
+}
@@ -1,3 +1,6 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
 use nvim_oxi as oxi;
 use nvim_oxi::api::{self, opts::*, types::*, Buffer};
 
@@ -6,7 +9,63 @@ fn add_highlight() {
     let mut buf = Buffer::current();
     let id = api::create_namespace("Foo");
     let res = buf.add_highlight(id, "Normal", 0, 0, 1);
-    assert!(res.is_ok(), "{res:?}");
+    assert_eq!(Ok(Some(id)), res);
+}
+
+#[oxi::test]
+fn add_highlight_ungrouped() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo"]).unwrap();
+
+    let res = buf.add_highlight(-1, "Normal", 0, 0, -1);
+    assert_eq!(Ok(None), res);
+}
+
+#[oxi::test]
+fn add_highlight_hl_constant() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo"]).unwrap();
+
+    let id = api::create_namespace("Foo");
+    let res = buf.add_highlight(id, api::hl::COMMENT, 0, 0, -1);
+    assert_eq!(Ok(Some(id)), res);
+
+    let start = ExtmarkPosition::ByTuple((0, 0));
+    let end = ExtmarkPosition::End;
+    let opts = GetExtmarksOpts::builder().details(true).build();
+    let (_, _, _, infos) =
+        buf.get_extmarks(id, start, end, &opts).unwrap().next().unwrap();
+
+    assert_eq!(Some(String::from("Comment")), infos.unwrap().hl_group);
+}
+
+#[oxi::test]
+fn add_highlight_new_source_id() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo", "bar"]).unwrap();
+
+    let src_id = buf.add_highlight(0, "Normal", 0, 0, -1).unwrap().unwrap();
+    assert_ne!(0, u32::from(src_id));
+
+    let res = buf.add_highlight(src_id, "Normal", 1, 0, -1);
+    assert_eq!(Ok(Some(src_id)), res);
+
+    let other_ns = api::create_namespace("Foo");
+    buf.add_highlight(other_ns, "Normal", 0, 0, -1).unwrap();
+
+    let count = |buf: &Buffer, ns_id| {
+        let start = ExtmarkPosition::ByTuple((0, 0));
+        let end = ExtmarkPosition::End;
+        let opts = GetExtmarksOpts::default();
+        buf.get_extmarks(ns_id, start, end, &opts).unwrap().count()
+    };
+
+    assert_eq!(2, count(&buf, src_id));
+
+    buf.clear_namespace(src_id, 0, usize::MAX).unwrap();
+
+    assert_eq!(0, count(&buf, src_id));
+    assert_eq!(1, count(&buf, other_ns));
 }
 
 #[oxi::test]
@@ -17,6 +76,78 @@ fn clear_namespace() {
     assert_eq!(Ok(()), res);
 }
 
+#[oxi::test]
+fn clear_namespace_inverted_range() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo", "bar"]).unwrap();
+
+    let id = api::create_namespace("Foo");
+    buf.set_extmark(id, 0, 0, &Default::default()).unwrap();
+
+    let err = buf.clear_namespace(id, 1, 0).unwrap_err();
+    assert_eq!("start 1 > end 0", err.to_string());
+
+    let start = ExtmarkPosition::ByTuple((0, 0));
+    let end = ExtmarkPosition::ByTuple((0, 0));
+    let opts = GetExtmarksOpts::default();
+    assert_eq!(1, buf.get_extmarks(id, start, end, &opts).unwrap().count());
+}
+
+#[oxi::test]
+fn get_extmarks_reverse() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo", "bar"]).unwrap();
+
+    let id = api::create_namespace("Foo");
+    let opts = SetExtmarkOpts::default();
+    let first = buf.set_extmark(id, 0, 0, &opts).unwrap();
+    let second = buf.set_extmark(id, 1, 2, &opts).unwrap();
+
+    let start = ExtmarkPosition::ByTuple((1, 5));
+    let end = ExtmarkPosition::ByTuple((0, 0));
+    let ids = buf
+        .get_extmarks(id, start, end, &Default::default())
+        .unwrap()
+        .map(|(id, ..)| id)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![second, first], ids);
+}
+
+#[oxi::test]
+fn get_namespace_id() {
+    let name = "get_namespace_id";
+    assert_eq!(None, api::get_namespace_id(name));
+
+    let id = api::create_namespace(name);
+    assert_eq!(Some(id), api::get_namespace_id(name));
+}
+
+#[oxi::test]
+fn get_extmarks_sorted() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo", "bar", "baz"]).unwrap();
+
+    let ns_id = api::create_namespace("Foo");
+    let opts = SetExtmarkOpts::default();
+    buf.set_extmark(ns_id, 2, 0, &opts).unwrap();
+    buf.set_extmark(ns_id, 0, 1, &opts).unwrap();
+    buf.set_extmark(ns_id, 1, 2, &opts).unwrap();
+    buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+
+    // Querying from the end to the start returns the extmarks in reverse
+    // traversal order.
+    let start = ExtmarkPosition::End;
+    let end = ExtmarkPosition::ByTuple((0, 0));
+
+    let positions = buf
+        .get_extmarks_sorted(ns_id, start, end, &Default::default())
+        .unwrap()
+        .map(|(_, row, col, _)| (row, col))
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![(0, 0), (0, 1), (1, 2), (2, 0)], positions);
+}
+
 #[oxi::test]
 fn get_extmarks() {
     let mut buf = Buffer::current();
@@ -125,6 +256,47 @@ fn set_decoration_provider() {
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
 }
 
+#[oxi::test]
+fn set_decoration_provider_line_highlights() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo bar"]).unwrap();
+
+    let lines = Rc::new(Cell::new(0));
+    let id = api::create_namespace("Foo");
+
+    let opts = DecorationProviderOpts::builder()
+        .on_line_highlights(id, {
+            let lines = Rc::clone(&lines);
+            move |(_, _, _, row)| {
+                lines.set(lines.get() + 1);
+                // Out of order on purpose, the run at 1..3 is still merged.
+                let mut hls = vec![(2, "Error".into()), (1, "Error".into())];
+                if row == 0 {
+                    hls.push((4, "Todo".into()));
+                }
+                Ok(hls)
+            }
+        })
+        .build();
+
+    assert_eq!(Ok(()), api::set_decoration_provider(id, &opts));
+
+    api::command("redraw!").unwrap();
+    assert!(lines.get() > 0);
+
+    let attr = |col: oxi::Integer| {
+        api::call_function::<_, oxi::Integer>("screenattr", (1, col + 1))
+            .unwrap()
+    };
+
+    let error = attr(1);
+    assert_eq!(error, attr(2));
+    assert_ne!(attr(0), error);
+    assert_ne!(attr(3), error);
+    assert_ne!(attr(4), error);
+    assert_ne!(attr(4), attr(0));
+}
+
 #[oxi::test]
 fn set_get_del_extmark() {
     let mut buf = Buffer::current();
@@ -152,7 +324,7 @@ fn set_get_del_extmark() {
     let res = buf.set_extmark(ns_id, 0, 0, &opts);
     assert!(res.is_ok(), "{res:?}");
 
-    let extmark_id = res.unwrap();
+    let extmark_id: ExtmarkId = res.unwrap();
 
     let opts = GetExtmarkByIdOpts::builder().details(true).build();
     let got = buf.get_extmark_by_id(ns_id, extmark_id, &opts);
@@ -178,3 +350,95 @@ fn set_get_del_extmark() {
     let res = buf.del_extmark(ns_id, extmark_id);
     assert_eq!(Ok(()), res);
 }
+
+#[oxi::test]
+fn get_extmark_by_u32_id() {
+    let mut buf = Buffer::current();
+    let ns_id = api::create_namespace("Foo");
+
+    let extmark_id =
+        buf.set_extmark(ns_id, 0, 0, &Default::default()).unwrap();
+    let raw_id = u32::from(extmark_id);
+
+    assert_eq!(
+        Ok((0, 0, None)),
+        buf.get_extmark_by_id(ns_id, raw_id, &Default::default())
+    );
+    assert_eq!(Ok(()), buf.del_extmark(ns_id, raw_id));
+}
+
+#[oxi::test]
+fn update_extmark_by_id() {
+    let mut buf = Buffer::current();
+    buf.set_lines(0, 1, true, ["foo", "bar"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let extmark_id =
+        buf.set_extmark(ns_id, 0, 1, &Default::default()).unwrap();
+
+    let opts = SetExtmarkOpts::builder().id(extmark_id).build();
+    assert_eq!(Ok(extmark_id), buf.set_extmark(ns_id, 1, 2, &opts));
+
+    assert_eq!(
+        Ok((1, 2, None)),
+        buf.get_extmark_by_id(ns_id, extmark_id, &Default::default())
+    );
+
+    let start = ExtmarkPosition::ByTuple((0, 0));
+    let end = ExtmarkPosition::End;
+    let count = buf
+        .get_extmarks(ns_id, start, end, &Default::default())
+        .unwrap()
+        .count();
+    assert_eq!(1, count);
+}
+
+#[oxi::test]
+fn virtual_text_layer() {
+    let buf = Buffer::current();
+    let ns_id = api::create_namespace("VirtualTextLayer");
+    let mut layer = api::VirtualTextLayer::new(buf.clone(), ns_id);
+
+    let first = layer.set(0, [("foo", ["Comment"])]).unwrap();
+    let second = layer.set(0, [("bar", ["Comment"])]).unwrap();
+    assert_eq!(first, second);
+
+    let start = ExtmarkPosition::ByTuple((0, 0));
+    let end = ExtmarkPosition::End;
+    let count = |buf: &Buffer| {
+        buf.get_extmarks(ns_id, start, end, &Default::default())
+            .unwrap()
+            .count()
+    };
+    assert_eq!(1, count(&buf));
+
+    assert_eq!(Ok(true), layer.clear(0));
+    assert_eq!(Ok(false), layer.clear(0));
+    assert_eq!(0, count(&buf));
+
+    layer.set(0, [("baz", ["Comment"])]).unwrap();
+    assert_eq!(Ok(()), layer.clear_all());
+    assert_eq!(0, count(&buf));
+}
+
+#[oxi::test]
+fn typed_namespace() {
+    let mut buf = Buffer::current();
+
+    let ns: Namespace = api::create_namespace("Typed");
+    assert_eq!(ns, api::create_namespace("Typed"));
+    assert!(
+        api::get_namespaces().any(|(name, id)| name == "Typed" && id == ns)
+    );
+
+    let id = buf.set_extmark(ns, 0, 0, &Default::default()).unwrap();
+    let (row, col, _) =
+        buf.get_extmark_by_id(ns, id, &Default::default()).unwrap();
+    assert_eq!((0, 0), (row, col));
+
+    buf.clear_namespace(ns, 0, usize::MAX).unwrap();
+
+    let raw_id = u32::from(ns);
+    assert_eq!(ns, Namespace::from(raw_id));
+    assert!(buf.get_extmark_by_id(raw_id, id, &Default::default()).is_err());
+}
@@ -1,6 +1,16 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
 use all_asserts::*;
-use nvim_oxi as oxi;
 use nvim_oxi::api::{self, opts::*, types::*, Buffer};
+use nvim_oxi::{
+    self as oxi,
+    Array,
+    Dictionary,
+    FromObject,
+    Object,
+    ObjectKind,
+};
 
 #[oxi::test]
 fn chan_send_fail() {
@@ -41,6 +51,41 @@ fn user_command_with_count() {
     assert!(res.is_ok(), "{res:?}");
 }
 
+#[oxi::test]
+fn user_command_custom_completion() {
+    let opts = CreateCommandOpts::builder()
+        .nargs(CommandNArgs::Any)
+        .complete(|arg_lead: &str, _cmd_line: &str, _cursor_pos: usize| {
+            Ok(["foo", "bar", "baz"]
+                .into_iter()
+                .filter(|cand| cand.starts_with(arg_lead))
+                .map(String::from)
+                .collect())
+        })
+        .build();
+    api::create_user_command("Foo", ":", &opts).unwrap();
+
+    let res = api::call_function::<_, Vec<String>>(
+        "getcompletion",
+        ("Foo b", "cmdline"),
+    );
+    assert_eq!(Ok(vec!["bar".to_owned(), "baz".to_owned()]), res);
+
+    let opts = CreateCommandOpts::builder()
+        .nargs(CommandNArgs::Any)
+        .complete(|_: &str, _: &str, _: usize| -> api::Result<Vec<String>> {
+            panic!("oops")
+        })
+        .build();
+    api::create_user_command("Bar", ":", &opts).unwrap();
+
+    let res = api::call_function::<_, Vec<String>>(
+        "getcompletion",
+        ("Bar b", "cmdline"),
+    );
+    assert_eq!(Ok(vec![]), res);
+}
+
 #[oxi::test]
 fn eval_statusline() {
     let opts = EvalStatuslineOpts::builder().highlights(true).build();
@@ -48,6 +93,111 @@ fn eval_statusline() {
     assert_eq!(Ok("foo".into()), res.map(|infos| infos.str));
 }
 
+#[oxi::test]
+fn exec_lua() {
+    let args = [Object::from(1), Object::from("foo"), Object::from(true)];
+    assert_eq!(Ok(3), api::exec_lua::<u8>("return select('#', ...)", args));
+
+    let res = api::exec_lua::<String>("return ...", [Object::from("bar")]);
+    assert_eq!(Ok("bar".into()), res);
+
+    assert!(api::exec_lua::<u8>("return 'baz'", []).is_err());
+}
+
+#[oxi::test]
+fn lua_chunk() {
+    let code = "local a, b = ...; return a * b";
+    let chunk = api::LuaChunk::compile(code).unwrap();
+
+    for n in 0..1000 {
+        let res = chunk.call::<_, i64>((n, 2)).unwrap();
+        let args = [Object::from(n), Object::from(2)];
+        assert_eq!(Ok(res), api::exec_lua::<i64>(code, args));
+        assert_eq!(n * 2, res);
+    }
+
+    let err = api::LuaChunk::compile("return +").unwrap_err();
+    assert!(err.to_string().starts_with("LuaChunk:1:"), "{err}");
+}
+
+#[oxi::test]
+fn lua_chunk_faster_than_exec_lua() {
+    use std::time::Instant;
+
+    // Big enough that parsing it dominates the cost of the call itself.
+    let code = format!(
+        "local a, b = ...\n{}return a * b",
+        "local _ = { 1, 2, 3, 'four', { five = 5 } }\n".repeat(50),
+    );
+    let chunk = api::LuaChunk::compile(&code).unwrap();
+
+    let start = Instant::now();
+    for n in 0..1000 {
+        chunk.call::<_, i64>((n, 2)).unwrap();
+    }
+    let chunk_time = start.elapsed();
+
+    let start = Instant::now();
+    for n in 0..1000 {
+        let args = [Object::from(n), Object::from(2)];
+        api::exec_lua::<i64>(&code, args).unwrap();
+    }
+    let exec_lua_time = start.elapsed();
+
+    assert_lt!(chunk_time, exec_lua_time);
+}
+
+#[oxi::test]
+fn exec_lua_object() {
+    let obj = api::exec_lua_object(
+        "local name, n = ...; return { name = name, double = n * 2 }",
+        ("foo", 21),
+    )
+    .unwrap();
+    assert_eq!(ObjectKind::Dictionary, obj.kind());
+
+    let dict = Dictionary::from_obj(obj).unwrap();
+    assert_eq!(Some(&Object::from("foo")), dict.get(&"name"));
+    assert_eq!(Some(&Object::from(42)), dict.get(&"double"));
+
+    let obj = api::exec_lua_object("return nil", Array::new()).unwrap();
+    assert_eq!(ObjectKind::Nil, obj.kind());
+}
+
+#[oxi::test]
+fn feedkeys_and_wait() {
+    let buf = api::create_buf(true, true).unwrap();
+    api::set_current_buf(&buf).unwrap();
+
+    let keys = api::replace_termcodes("ifoo<Esc>", true, false, true);
+    api::feedkeys_and_wait(keys, FeedkeysMode::NoRemap).unwrap();
+
+    assert_eq!(
+        vec![oxi::String::from("foo")],
+        buf.get_lines(0, 1, true).unwrap().collect::<Vec<_>>()
+    );
+    assert_eq!(Mode::Normal, api::get_mode().unwrap().mode);
+}
+
+#[oxi::test]
+fn feedkeys_and_wait_restores_errmsg() {
+    api::set_vvar("errmsg", "previous").unwrap();
+
+    let keys = api::replace_termcodes("<Esc>", true, false, true);
+    assert_eq!(Ok(()), api::feedkeys_and_wait(keys, FeedkeysMode::NoRemap));
+    assert_eq!(Ok("previous".into()), api::get_vvar::<String>("errmsg"));
+
+    let keys = api::replace_termcodes(
+        ":call NoSuchFunction()<CR>",
+        true,
+        false,
+        true,
+    );
+    let err = api::feedkeys_and_wait(keys, FeedkeysMode::NoRemap).unwrap_err();
+    assert!(err.to_string().contains("E117"), "{err}");
+    assert_eq!(Ok("previous".into()), api::get_vvar::<String>("errmsg"));
+}
+
 #[oxi::test]
 fn get_chan_info() {
     let res = api::get_chan_info(0);
@@ -69,6 +219,44 @@ fn get_context() {
     assert!(res.is_ok());
 }
 
+#[oxi::test]
+fn set_current_win_float() {
+    let buf = api::create_buf(false, true).unwrap();
+
+    for focusable in [true, false] {
+        let config = WindowConfig::builder()
+            .relative(WindowRelativeTo::Editor)
+            .height(5)
+            .width(10)
+            .row(1)
+            .col(1)
+            .focusable(focusable)
+            .build();
+
+        let win = api::open_win(&buf, false, &config).unwrap();
+        assert_ne!(win, api::Window::current());
+
+        assert_eq!(Ok(()), api::set_current_win(&win));
+        assert_eq!(win, api::Window::current());
+
+        win.close(true).unwrap();
+    }
+}
+
+#[oxi::test]
+fn set_get_current_dir() {
+    let dir = std::env::temp_dir();
+    assert_eq!(Ok(()), api::set_current_dir(&dir));
+
+    // The temporary directory can be a symlink, e.g. on macOS, and on Windows
+    // `canonicalize` returns a verbatim `\\?\C:\...` path while Neovim
+    // doesn't, so both sides are canonicalized before comparing them.
+    let cwd = api::get_current_dir().unwrap();
+    assert_eq!(dir.canonicalize().unwrap(), cwd.canonicalize().unwrap());
+
+    assert!(api::set_current_dir("/this/path/does/not/exist").is_err());
+}
+
 #[oxi::test]
 fn get_highlights() {
     let (name, _) = api::get_color_map().next().unwrap();
@@ -76,6 +264,19 @@ fn get_highlights() {
     assert_eq!(api::get_hl_by_id(id, true), api::get_hl_by_name(&name, true));
 }
 
+#[cfg(feature = "neovim-nightly")]
+#[oxi::test]
+fn get_hl_ns() {
+    let win = api::get_current_win();
+    let opts = GetHlNsOpts::builder().window(win).build();
+    assert_eq!(Ok(-1), api::get_hl_ns(&opts));
+
+    let ns_id = api::create_namespace("foo");
+    api::command(&format!("lua vim.api.nvim_win_set_hl_ns(0, {ns_id})"))
+        .unwrap();
+    assert_eq!(Ok(i64::from(ns_id)), api::get_hl_ns(&opts));
+}
+
 #[oxi::test]
 fn get_mode() {
     let got_mode = api::get_mode().unwrap();
@@ -98,6 +299,43 @@ fn get_option_info() {
 #[oxi::test]
 fn get_runtime_file() {
     assert!(api::get_runtime_file("*", true).unwrap().next().is_some());
+
+    let name = std::path::PathBuf::from("syntax").join("*.vim");
+    let mut files = api::get_runtime_file(&name, true).unwrap();
+    assert!(files.all(|file| file.extension() == Some("vim".as_ref())));
+}
+
+#[oxi::test]
+fn hl_foreground_hex() {
+    let opts = SetHighlightOpts::builder()
+        .foreground(Color::from_hex("#ff8800").unwrap())
+        .build();
+    api::set_hl(0, "MatchParen", &opts).unwrap();
+
+    let infos = api::get_hl_by_name("MatchParen", true).unwrap();
+    assert_eq!(Some(0xff8800), infos.foreground);
+}
+
+#[oxi::test]
+fn hl_foreground_named() {
+    let opts = SetHighlightOpts::builder()
+        .foreground(Color::try_from("Red").unwrap())
+        .background(Color::try_from("NONE").unwrap())
+        .build();
+    api::set_hl(0, "MatchParen", &opts).unwrap();
+
+    let infos = api::get_hl_by_name("MatchParen", true).unwrap();
+    assert_eq!(Some(0xff0000), infos.foreground);
+    assert_eq!(None, infos.background);
+}
+
+#[oxi::test]
+fn hl_foreground_invalid_hex() {
+    let opts = Color::try_from("#ff88")
+        .map(|fg| SetHighlightOpts::builder().foreground(fg).build());
+
+    let err = opts.unwrap_err().to_string();
+    assert!(err.contains("#ff88"), "{err}");
 }
 
 #[oxi::test]
@@ -109,11 +347,83 @@ fn hl_underline() {
     assert_eq!(Some(true), infos.underline);
 }
 
+#[oxi::test]
+fn hl_diff() {
+    let fg = Color::from_hex("#ff8800").unwrap();
+
+    let opts = SetHighlightOpts::builder()
+        .foreground(fg.clone())
+        .background(Color::from_hex("#000000").unwrap())
+        .build();
+    api::set_hl(0, "OxiDiffA", &opts).unwrap();
+
+    let opts = SetHighlightOpts::builder()
+        .foreground(fg)
+        .background(Color::from_hex("#ffffff").unwrap())
+        .build();
+    api::set_hl(0, "OxiDiffB", &opts).unwrap();
+
+    let a = api::get_hl_by_name("OxiDiffA", true).unwrap();
+    let b = api::get_hl_by_name("OxiDiffB", true).unwrap();
+    assert_eq!(vec![HighlightField::Background], a.diff(&b));
+    assert!(a.diff(&a).is_empty());
+}
+
+#[oxi::test]
+fn link_hl() {
+    assert_eq!(Ok(()), api::link_hl("MyGroup", "Comment", false));
+
+    let comment = api::get_hl_by_name("Comment", true).unwrap();
+    assert_eq!(Ok(comment), api::get_hl_by_name("MyGroup", true));
+}
+
 #[oxi::test]
 fn list_runtime_paths() {
     assert!(api::list_runtime_paths().unwrap().next().is_some());
 }
 
+#[oxi::test]
+fn list_uis() {
+    assert_eq!(Ok(Vec::new()), api::list_uis());
+
+    // Attach a UI through an RPC channel connected to this same instance,
+    // then let the event loop handle the request.
+    let chan = api::exec_lua::<u32>(
+        "local addr = vim.fn.serverstart()
+        local chan = vim.fn.sockconnect('pipe', addr, { rpc = true })
+        vim.rpcnotify(chan, 'nvim_ui_attach', 80, 24, {
+            rgb = true,
+            ext_popupmenu = true,
+        })
+        vim.wait(1000, function()
+            return #vim.api.nvim_list_uis() > 0
+        end)
+        return chan",
+        [],
+    )
+    .unwrap();
+
+    let uis = api::list_uis().unwrap();
+    assert_eq!(1, uis.len(), "{uis:?}");
+
+    let ui = &uis[0];
+    assert_eq!(80, ui.width);
+    assert_eq!(24, ui.height);
+    assert!(ui.rgb);
+    assert!(ui.ext_popupmenu);
+    assert!(!ui.ext_multigrid);
+    assert!(ui.chan_id.is_some());
+
+    api::call_function::<_, oxi::Integer>("chanclose", (chan,)).unwrap();
+}
+
+#[oxi::test]
+fn notify_once() {
+    assert_eq!(Ok(true), api::notify_once("foo", LogLevel::Info));
+    assert_eq!(Ok(false), api::notify_once("foo", LogLevel::Info));
+    assert_eq!(Ok(Some("foo".into())), api::exec("messages", true));
+}
+
 #[oxi::test]
 fn set_get_del_current_line() {
     let res = api::set_current_line("foo");
@@ -144,6 +454,101 @@ fn set_get_del_keymap() {
     assert_eq!(Ok(()), res);
 }
 
+#[oxi::test]
+fn set_keymaps() {
+    let mappings = (0..20).map(|i| {
+        (
+            Mode::Normal,
+            format!("<Leader>k{i:02}"),
+            "j",
+            SetKeymapOpts::default(),
+        )
+    });
+    assert_eq!(Ok(()), api::set_keymaps(mappings));
+
+    let count = api::get_keymap(Mode::Normal)
+        .filter(|keymap| keymap.lhs.starts_with("\\k"))
+        .count();
+    assert_eq!(20, count);
+
+    let unique = SetKeymapOpts::builder().unique(true).build();
+    let res = api::set_keymaps([
+        (Mode::Normal, "<Leader>knew", "j", SetKeymapOpts::default()),
+        (Mode::Normal, "<Leader>k00", "k", unique),
+    ]);
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("<Leader>k00"), "{err}");
+}
+
+#[oxi::test]
+fn set_keymap_rhs_keycodes() {
+    // `nvim_set_keymap` already translates the keycodes in the right-hand
+    // side, and `<lt>` keeps a literal `<`.
+    let rhs = "ia<lt>b><Esc>";
+    api::set_keymap(Mode::Normal, "<Leader>x", rhs, &Default::default())
+        .unwrap();
+
+    api::feedkeys_and_wait("\\x", FeedkeysMode::Remap).unwrap();
+    assert_eq!(Ok("a<b>".into()), api::get_current_line());
+    assert_eq!(Mode::Normal, api::get_mode().unwrap().mode);
+
+    api::del_keymap(Mode::Normal, "<Leader>x").unwrap();
+}
+
+#[oxi::test]
+fn invoke_keymap_callback() {
+    let count = Rc::new(Cell::new(0));
+
+    let opts = SetKeymapOpts::builder()
+        .callback({
+            let count = Rc::clone(&count);
+            move |_| {
+                count.set(count.get() + 1);
+                Ok(())
+            }
+        })
+        .build();
+
+    api::set_keymap(Mode::Normal, "<Plug>(invoke-me)", "", &opts).unwrap();
+    api::set_keymap(
+        Mode::Normal,
+        "<Plug>(no-callback)",
+        "j",
+        &Default::default(),
+    )
+    .unwrap();
+
+    let keymaps = api::get_keymap(Mode::Normal).collect::<Vec<_>>();
+
+    let keymap = keymaps
+        .iter()
+        .find(|keymap| keymap.lhs == "<Plug>(invoke-me)")
+        .unwrap();
+    assert!(keymap.has_callback());
+    assert_eq!(Ok(()), keymap.invoke());
+    assert_eq!(1, count.get());
+
+    let keymap = keymaps
+        .iter()
+        .find(|keymap| keymap.lhs == "<Plug>(no-callback)")
+        .unwrap();
+    assert!(!keymap.has_callback());
+    assert!(keymap.invoke().is_err());
+
+    api::del_keymap(Mode::Normal, "<Plug>(invoke-me)").unwrap();
+    api::del_keymap(Mode::Normal, "<Plug>(no-callback)").unwrap();
+
+    // The mapping is gone, so invoking it is an error even though the
+    // `KeymapInfos` still holds a reference to its callback.
+    let keymap = keymaps
+        .iter()
+        .find(|keymap| keymap.lhs == "<Plug>(invoke-me)")
+        .unwrap();
+    let err = keymap.invoke().unwrap_err();
+    assert!(err.to_string().contains("deleted"), "{err}");
+    assert_eq!(1, count.get());
+}
+
 #[oxi::test]
 fn set_get_del_mark() {
     let mut buf = Buffer::current();
@@ -157,7 +562,16 @@ fn set_get_del_mark() {
     );
 
     let res = api::del_mark('A');
-    assert_eq!(Ok(()), res);
+    assert_eq!(Ok(true), res);
+
+    let res = api::del_mark('A');
+    assert_eq!(Ok(false), res);
+}
+
+#[oxi::test]
+fn get_mark_not_global() {
+    assert!(api::get_mark('a', &Default::default()).is_err());
+    assert!(api::get_mark('.', &Default::default()).is_err());
 }
 
 #[oxi::test]
@@ -167,6 +581,19 @@ fn set_get_del_var() {
     assert_eq!(Ok(()), api::del_var("foo"));
 }
 
+#[cfg(not(feature = "neovim-0-7"))]
+#[oxi::test]
+fn set_buf_win_option_value() {
+    let buf = api::create_buf(true, false).unwrap();
+    api::set_buf_option_value(&buf, "modifiable", false).unwrap();
+    assert_eq!(Ok(false), buf.get_option::<bool>("modifiable"));
+    assert_eq!(Ok(true), Buffer::current().get_option::<bool>("modifiable"));
+
+    let win = api::Window::current();
+    api::set_win_option_value(&win, "cursorline", true).unwrap();
+    assert_eq!(Ok(true), win.get_option::<bool>("cursorline"));
+}
+
 #[oxi::test]
 fn set_get_option() {
     api::set_option("modified", true).unwrap();
@@ -176,7 +603,68 @@ fn set_get_option() {
     assert!(!api::get_option::<bool>("modified").unwrap());
 }
 
+#[oxi::test]
+fn with_options() {
+    let eventignore = || api::get_option::<String>("eventignore").unwrap();
+    let initial = eventignore();
+
+    let res = api::with_options(&[("eventignore", "all".into())], || {
+        assert_eq!("all", eventignore());
+        Ok(42)
+    });
+    assert_eq!(Ok(42), res);
+    assert_eq!(initial, eventignore());
+
+    // The options are also restored if the closure fails.
+    let res = api::with_options(&[("eventignore", "all".into())], || {
+        api::command("__OxiNoSuchCommand")
+    });
+    assert!(res.is_err());
+    assert_eq!(initial, eventignore());
+}
+
 #[oxi::test]
 fn strwidth() {
     assert_eq!(Ok(2), api::strwidth("｜"));
 }
+
+#[oxi::test]
+fn subscribe_unsubscribe_no_rpc_channel() {
+    // The test instance isn't connected to any RPC client, so both should
+    // fail instead of aborting Neovim.
+    assert!(api::subscribe(0, "foo").is_err());
+    assert!(api::unsubscribe(0, "foo").is_err());
+    assert!(api::subscribe(42, "foo").is_err());
+    assert!(api::unsubscribe(42, "foo").is_err());
+}
+
+#[oxi::test]
+fn subscribe_unsubscribe() {
+    // Connect an RPC channel to this same instance, so that the broadcasts it
+    // receives are executed as API calls on the other side.
+    let chan = api::exec_lua::<u32>(
+        "local addr = vim.fn.serverstart()
+        return vim.fn.sockconnect('pipe', addr, { rpc = true })",
+        [],
+    )
+    .unwrap();
+
+    let broadcast_and_wait = |value: u8| {
+        api::exec_lua_object(
+            "local value = ...
+            vim.rpcnotify(0, 'nvim_set_var', 'broadcast', value)
+            vim.wait(200, function() return vim.g.broadcast == value end)",
+            Array::from((value,)),
+        )
+        .unwrap();
+        api::get_var::<u8>("broadcast").ok()
+    };
+
+    assert_eq!(Ok(()), api::subscribe(chan, "nvim_set_var"));
+    assert_eq!(Some(1), broadcast_and_wait(1));
+
+    assert_eq!(Ok(()), api::unsubscribe(chan, "nvim_set_var"));
+    assert_eq!(Some(1), broadcast_and_wait(2));
+
+    api::call_function::<_, oxi::Integer>("chanclose", (chan,)).unwrap();
+}
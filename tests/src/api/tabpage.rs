@@ -1,4 +1,6 @@
-use nvim_oxi::{self as oxi, api::TabPage};
+use nvim_oxi as oxi;
+#[allow(unused_imports)]
+use nvim_oxi::api::{self, TabPage, Window};
 
 #[oxi::test]
 fn get_list_wins() {
@@ -33,3 +35,20 @@ fn tabpage_set_get_del_var() {
     assert_eq!(Ok(42), tab.get_var("foo"));
     assert_eq!(Ok(()), tab.del_var("foo"));
 }
+
+#[cfg(feature = "neovim-nightly")]
+#[oxi::test]
+fn tabpage_set_win() {
+    let mut tab = TabPage::current();
+    let first = Window::current();
+
+    api::command("split").unwrap();
+    assert_ne!(first, tab.get_win().unwrap());
+
+    assert_eq!(Ok(()), tab.set_win(&first));
+    assert_eq!(Ok(first), tab.get_win());
+
+    api::command("tabnew").unwrap();
+    let other = Window::current();
+    assert!(tab.set_win(&other).is_err());
+}
@@ -1,6 +1,9 @@
-use nvim_oxi as oxi;
+use std::cell::RefCell;
+use std::rc::Rc;
+
 #[allow(unused_imports)]
 use nvim_oxi::api::{self, opts::*, types::*};
+use nvim_oxi::{self as oxi, FromObject};
 
 #[oxi::test]
 fn call_function() {
@@ -8,6 +11,115 @@ fn call_function() {
     assert_eq!(Ok(7), res);
 }
 
+#[oxi::test]
+fn call_dict_function() {
+    api::exec(
+        "let g:counter = {'n': 41}
+        function! g:counter.next() dict
+            return self.n + 1
+        endfunction",
+        false,
+    )
+    .unwrap();
+
+    let res = api::call_dict_function::<_, u32>(
+        "g:counter".into(),
+        "next",
+        oxi::Array::new(),
+    );
+    assert_eq!(Ok(42), res);
+
+    let dict = oxi::Dictionary::from_iter([(
+        "double",
+        oxi::Function::from_fn(|n: u32| Ok::<_, oxi::Error>(n * 2)),
+    )]);
+
+    let res = api::call_dict_function::<_, u32>(dict.into(), "double", (21,));
+    assert_eq!(Ok(42), res);
+}
+
+#[oxi::test]
+fn complete() {
+    let buf = api::create_buf(true, true).unwrap();
+    api::set_current_buf(&buf).unwrap();
+
+    let res = Rc::new(RefCell::new(None));
+
+    let opts = SetKeymapOpts::builder()
+        .callback({
+            let res = Rc::clone(&res);
+            move |_| {
+                let items = vec![
+                    CompleteItem {
+                        menu: Some("oxi".into()),
+                        ..CompleteItem::from("foobar")
+                    },
+                    "foobaz".into(),
+                ];
+                *res.borrow_mut() =
+                    Some(api::complete(0, items, &Default::default()));
+                Ok(())
+            }
+        })
+        .build();
+
+    api::set_keymap(Mode::Insert, "<Plug>(oxi-complete)", "", &opts).unwrap();
+
+    // The first match is inserted as soon as the completion starts.
+    let keys = api::replace_termcodes(
+        "i<Plug>(oxi-complete)<Esc>",
+        true,
+        false,
+        true,
+    );
+    api::feedkeys_and_wait(keys, FeedkeysMode::Remap).unwrap();
+
+    assert_eq!(Some(Ok(())), res.borrow_mut().take());
+    assert_eq!(Ok("foobar".into()), api::get_current_line());
+}
+
+#[oxi::test]
+fn confirm() {
+    let choices = ["&Yes", "&No", "&Cancel"];
+
+    api::input("n").unwrap();
+    assert_eq!(Ok(Some(1)), api::confirm("Save?", &choices, 0));
+
+    api::input("<CR>").unwrap();
+    assert_eq!(Ok(Some(2)), api::confirm("Save?", &choices, 2));
+
+    api::input("<Esc>").unwrap();
+    assert_eq!(Ok(None), api::confirm("Save?", &choices, 0));
+}
+
+#[oxi::test]
+fn get_api_info() {
+    let info = api::get_api_info().unwrap();
+    assert!(info.version.major > 0 || info.version.minor >= 7);
+    assert!(info.version.api_level > 0);
+    assert!(info.metadata.get(&"functions").is_some());
+}
+
+#[oxi::test]
+fn has() {
+    assert!(api::has("nvim"));
+    assert!(api::has("nvim-0.7"));
+    assert!(!api::has("nvim-42.0"));
+    assert!(!api::has("__oxi_no_such_feature"));
+}
+
+#[oxi::test]
+fn input_prompt() {
+    api::input("bar<CR>").unwrap();
+    assert_eq!(Ok(Some("foobar".into())), api::input_prompt("> ", "foo"));
+
+    api::input("<CR>").unwrap();
+    assert_eq!(Ok(Some("".into())), api::input_prompt("> ", ""));
+
+    api::input("<Esc>").unwrap();
+    assert_eq!(Ok(None), api::input_prompt("> ", "foo"));
+}
+
 #[cfg(feature = "neovim-nightly")]
 #[oxi::test]
 fn cmd_basic() {
@@ -53,6 +165,95 @@ fn exec() {
     assert_eq!(Ok(Some("2".into())), add);
 }
 
+#[oxi::test]
+fn exec_output() {
+    let res = api::exec_output(":echo 'foo'").unwrap();
+    assert_eq!("foo", res.output);
+    assert_eq!(None, res.error);
+    assert!(!res.had_error);
+
+    let res = api::exec_output(":echo 'foo' | silent! call NoSuchFunction()");
+    let res = res.unwrap();
+    assert_eq!("foo", res.output);
+    assert_eq!(None, res.error);
+    assert!(res.had_error);
+}
+
+#[oxi::test]
+fn exec_output_error() {
+    api::set_vvar("errmsg", "previous").unwrap();
+
+    let res = api::exec_output("echo 'foo'\ncall NoSuchFunction()").unwrap();
+    assert_eq!("foo", res.output);
+    assert!(res.had_error);
+
+    let err = res.error.map(|err| err.to_string()).unwrap_or_default();
+    assert!(err.contains("E117"), "{err}");
+    assert!(!res.output.contains("E117"));
+
+    assert_eq!(Ok("previous".into()), api::get_vvar::<String>("errmsg"));
+}
+
+#[oxi::test]
+fn exec_output_warning() {
+    let mut buf = api::Buffer::current();
+    buf.set_option("readonly", true).unwrap();
+
+    let res = api::exec_output("normal! ifoo").unwrap();
+    assert!(res.had_error);
+
+    buf.set_option("readonly", false).unwrap();
+}
+
+#[oxi::test]
+fn lua_require() {
+    api::command(
+        "lua package.preload['oxi_leaf'] = function() return { answer = 42, \
+         name = 'oxi' } end",
+    )
+    .unwrap();
+
+    let leaf = api::lua_require("oxi_leaf").unwrap();
+    let leaf = oxi::Dictionary::from_obj(leaf).unwrap();
+
+    assert_eq!(Some(&oxi::Object::from(42)), leaf.get(&"answer"));
+    assert_eq!(Some(&oxi::Object::from("oxi")), leaf.get(&"name"));
+}
+
+#[oxi::test]
+fn lua_require_function() {
+    api::command(
+        "lua package.preload['oxi_upper'] = function() return string.upper \
+         end",
+    )
+    .unwrap();
+
+    let upper = api::lua_require_function("oxi_upper").unwrap();
+    assert_eq!(Ok("FOO".into()), upper.call::<_, String>(String::from("foo")));
+}
+
+#[oxi::test]
+fn require() {
+    let vim = api::require("vim").unwrap();
+
+    // `vim.inspect` is loaded lazily through the module's `__index`.
+    let inspect = vim.get::<_, oxi::LuaFunction>("inspect").unwrap();
+    assert_eq!(Ok("42".into()), inspect.call::<_, String>(42));
+
+    let api_table = vim.get::<_, oxi::LuaTable>("api").unwrap();
+    assert_ne!(vim, api_table);
+
+    assert!(api::require("__oxi_no_such_module").is_err());
+}
+
+#[oxi::test]
+fn lua_require_not_found() {
+    let err = api::lua_require("__oxi_no_such_module").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("module '__oxi_no_such_module' not found"));
+}
+
 #[cfg(feature = "neovim-nightly")]
 #[oxi::test]
 fn parse_cmd_basic() {
@@ -109,3 +109,119 @@ fn set_config() {
 
     assert_eq!(Ok(()), win.set_config(&config));
 }
+
+fn open_float(zindex: u32) -> Window {
+    let buf = api::create_buf(false, true).unwrap();
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(5)
+        .width(10)
+        .row(1)
+        .col(1)
+        .zindex(zindex)
+        .build();
+    api::open_win(&buf, false, &config).unwrap()
+}
+
+fn zindex(win: &Window) -> u32 {
+    win.get_config().unwrap().zindex.unwrap()
+}
+
+#[oxi::test]
+fn win_raise_focus() {
+    let mut lower = open_float(10);
+    let upper = open_float(20);
+
+    assert_eq!(Ok(()), lower.raise());
+    assert_eq!(21, zindex(&lower));
+    assert_eq!(20, zindex(&upper));
+
+    // Already on top.
+    assert_eq!(Ok(()), lower.raise());
+    assert_eq!(21, zindex(&lower));
+
+    assert_eq!(Ok(()), lower.focus());
+    assert_eq!(lower, Window::current());
+}
+
+#[oxi::test]
+fn get_position_relative() {
+    let parent = open_float(50);
+
+    let buf = api::create_buf(false, true).unwrap();
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Window(parent.clone()))
+        .height(2)
+        .width(5)
+        .row(2)
+        .col(3)
+        .build();
+    let child = api::open_win(&buf, false, &config).unwrap();
+
+    assert_eq!(
+        Ok(WinPosition { row: 2, col: 3 }),
+        child.get_position_relative(&parent)
+    );
+    assert_eq!(
+        Ok(WinPosition::default()),
+        parent.get_position_relative(&parent)
+    );
+    assert!(parent.get_position_relative(&child).is_err());
+}
+
+#[oxi::test]
+fn float_stack() {
+    let lower = open_float(50);
+    let upper = open_float(50);
+
+    let mut stack = api::FloatStack::new(100);
+    assert_eq!(Ok(()), stack.push(lower.clone()));
+    assert_eq!(Ok(()), stack.push(upper.clone()));
+    assert_eq!(Some(&upper), stack.top());
+    assert!(zindex(&lower) < zindex(&upper));
+
+    assert_eq!(Ok(()), stack.raise(&lower));
+    assert_eq!(Some(&lower), stack.top());
+    assert!(zindex(&lower) > zindex(&upper));
+    assert_eq!(vec![&upper, &lower], stack.windows().collect::<Vec<_>>());
+
+    upper.close(true).unwrap();
+    assert_eq!(vec![&lower], stack.windows().collect::<Vec<_>>());
+
+    let other = open_float(50);
+    assert!(stack.raise(&other).is_err());
+}
+
+#[oxi::test]
+fn get_config_relative_win() {
+    api::command("split").unwrap();
+    let split = Window::current();
+    api::command("wincmd p").unwrap();
+    assert_ne!(split, Window::current());
+
+    let buf = api::create_buf(false, true).unwrap();
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Window(split.clone()))
+        .height(5)
+        .width(10)
+        .row(1)
+        .col(1)
+        .build();
+    let float = api::open_win(&buf, false, &config).unwrap();
+
+    let got = float.get_config().unwrap();
+    assert_eq!(Some(split.clone()), got.win);
+    assert_eq!(Some(WindowRelativeTo::Window(split)), got.relative);
+
+    // A handle of 0 stands for the current window, and Neovim resolves it
+    // when opening the float.
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Window(Window::from(0)))
+        .height(5)
+        .width(10)
+        .row(1)
+        .col(1)
+        .build();
+    let float = api::open_win(&buf, false, &config).unwrap();
+    assert_eq!(Some(Window::current()), float.get_config().unwrap().win);
+}
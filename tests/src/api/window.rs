@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use nvim_oxi as oxi;
 use nvim_oxi::api::{self, types::*, Buffer, TabPage, Window};
 
@@ -27,6 +29,52 @@ fn close_hide() {
     assert_eq!(Ok(()), win.hide());
 }
 
+#[oxi::test]
+fn close_if_valid() {
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(10)
+        .width(5)
+        .row(1.5)
+        .col(1.5)
+        .build();
+
+    let win = api::open_win(&Buffer::current(), false, &config).unwrap();
+    assert_eq!(Ok(()), win.clone().close_if_valid());
+    assert!(!win.is_valid());
+    assert_eq!(Ok(()), win.close_if_valid());
+}
+
+#[oxi::test]
+fn get_cursor_invalid_window() {
+    let config = WindowConfig::builder()
+        .relative(WindowRelativeTo::Editor)
+        .height(10)
+        .width(5)
+        .row(1.5)
+        .col(1.5)
+        .build();
+
+    let win = api::open_win(&Buffer::current(), false, &config).unwrap();
+    assert_eq!(Ok((1, 0).into()), win.get_cursor());
+
+    win.clone().close(false).unwrap();
+    assert!(matches!(
+        win.get_cursor(),
+        Err(api::Error::InvalidWindow(w)) if w == win
+    ));
+}
+
+#[oxi::test]
+fn win_eval_statusline() {
+    api::set_option("statusline", "foo%=bar").unwrap();
+
+    let win = Window::current();
+    let infos = win.eval_statusline(&Default::default()).unwrap();
+    assert_eq!(win.get_width().unwrap(), infos.width);
+    assert!(infos.str.starts_with("foo") && infos.str.ends_with("bar"));
+}
+
 #[oxi::test]
 fn win_get_number() {
     assert_eq!(Ok(1), Window::current().get_number());
@@ -34,7 +82,9 @@ fn win_get_number() {
 
 #[oxi::test]
 fn get_position() {
-    assert_eq!(Ok((0, 0)), Window::current().get_position());
+    let pos = Window::current().get_position().unwrap();
+    assert_eq!((0, 0), (pos.row, pos.col));
+    assert_eq!((0, 0), pos.into());
 }
 
 #[oxi::test]
@@ -84,6 +134,42 @@ fn get_tabpage() {
     assert_eq!(Ok(TabPage::current()), Window::current().get_tabpage())
 }
 
+#[oxi::test]
+fn win_only() {
+    api::command("split").unwrap();
+    api::command("vsplit").unwrap();
+    api::command("split").unwrap();
+
+    let tabpage = TabPage::current();
+    assert_eq!(4, tabpage.list_wins().unwrap().count());
+
+    let win = Window::current();
+    assert_eq!(Ok(()), win.only());
+
+    let wins = tabpage.list_wins().unwrap().collect::<Vec<_>>();
+    assert_eq!(vec![win.clone()], wins);
+
+    // Already the only window.
+    assert_eq!(Ok(()), win.only());
+}
+
+#[oxi::test]
+fn save_restore_view() {
+    let mut buf = Buffer::current();
+    let lines = (1..=100).map(|n| n.to_string()).collect::<Vec<_>>();
+    buf.set_lines(0, 1, true, lines).unwrap();
+
+    let mut win = Window::current();
+    let view = win.save_view().unwrap();
+    assert_eq!(1, view.topline);
+
+    api::command("normal! 50Gzt").unwrap();
+    assert_eq!(50, win.save_view().unwrap().topline);
+
+    win.restore_view(&view).unwrap();
+    assert_eq!(view, win.save_view().unwrap());
+}
+
 #[oxi::test]
 fn set_get_cursor() {
     let mut buf = Buffer::current();
@@ -92,14 +178,62 @@ fn set_get_cursor() {
     let mut win = Window::current();
 
     assert_eq!(Ok(()), win.set_cursor(1, 2));
-    assert_eq!(Ok((1, 2)), win.get_cursor());
+    assert_eq!(Ok((1, 2).into()), win.get_cursor());
+
+    assert!(win.set_cursor(1, 42).is_err());
+    assert_eq!(Ok((1, 2).into()), win.get_cursor());
 
-    assert_eq!(Ok(()), win.set_cursor(1, 42));
-    assert_eq!(Ok((1, 2)), win.get_cursor());
+    assert!(win.set_cursor(2, 0).is_err());
+    assert_eq!(Ok((1, 2).into()), win.get_cursor());
 
     buf.set_lines(0, 1, true, [""]).unwrap();
 
-    assert_eq!(Ok((1, 0)), win.get_cursor());
+    assert_eq!(Ok((1, 0).into()), win.get_cursor());
+}
+
+#[oxi::test]
+fn set_cursor_no_scroll() {
+    let mut buf = Buffer::current();
+    let lines = (1..=100).map(|n| n.to_string()).collect::<Vec<_>>();
+    buf.set_lines(0, 1, true, lines).unwrap();
+
+    let mut win = Window::current();
+    api::command("normal! 50Gzt").unwrap();
+    win.set_option("scrolloff", 5).unwrap();
+
+    assert_eq!(Ok(()), win.set_cursor_no_scroll(56, 1));
+    assert_eq!(Ok((56, 1).into()), win.get_cursor());
+    assert_eq!(50, win.save_view().unwrap().topline);
+
+    // Moving further down, but still within the visible area.
+    win.set_cursor_no_scroll(60, 0).unwrap();
+    assert_eq!(Ok((60, 0).into()), win.get_cursor());
+    assert_eq!(50, win.save_view().unwrap().topline);
+
+    // Jumping far past the last visible line clamps the cursor to the last
+    // line that doesn't require scrolling, so even a redraw leaves the
+    // viewport untouched.
+    let last = api::call_function::<_, usize>("line", ("w$",)).unwrap();
+    assert!(last < 100);
+
+    assert_eq!(Ok(()), win.set_cursor_no_scroll(100, 1));
+    assert_eq!(Ok((last - 5, 1).into()), win.get_cursor());
+    assert_eq!(50, win.save_view().unwrap().topline);
+
+    api::command("redraw").unwrap();
+    assert_eq!(50, win.save_view().unwrap().topline);
+    assert_eq!(Ok((last - 5, 1).into()), win.get_cursor());
+
+    // The same goes for jumping above the first visible line.
+    assert_eq!(Ok(()), win.set_cursor_no_scroll(1, 0));
+    assert_eq!(Ok((55, 0).into()), win.get_cursor());
+
+    api::command("redraw").unwrap();
+    assert_eq!(50, win.save_view().unwrap().topline);
+
+    assert!(win.set_cursor_no_scroll(101, 0).is_err());
+
+    win.set_option("scrolloff", -1).unwrap();
 }
 
 #[oxi::test]
@@ -113,6 +247,64 @@ fn win_set_get_option() {
     assert!(!win.get_option::<bool>("spell").unwrap());
 }
 
+#[oxi::test]
+fn win_set_get_opt() {
+    let mut win = Window::current();
+
+    win.set_opt::<window_options::Number>(true).unwrap();
+    assert_eq!(Ok(true), win.get_opt::<window_options::Number>());
+    assert_eq!(Ok(true), win.get_option::<bool>("number"));
+
+    win.set_opt::<window_options::Winhighlight>("Normal:Comment".into())
+        .unwrap();
+    assert_eq!(
+        Ok(String::from("Normal:Comment")),
+        win.get_opt::<window_options::Winhighlight>()
+    );
+}
+
+#[oxi::test]
+fn win_set_get_winhighlight() {
+    let mut win = Window::current();
+
+    let map = HashMap::from([
+        ("Normal".to_owned(), "Comment".to_owned()),
+        ("CursorLine".to_owned(), "Visual".to_owned()),
+    ]);
+
+    win.set_winhighlight(&map).unwrap();
+    assert_eq!(
+        Ok(String::from("CursorLine:Visual,Normal:Comment")),
+        win.get_opt::<window_options::Winhighlight>()
+    );
+    assert_eq!(Ok(map), win.get_winhighlight());
+
+    win.set_winhighlight(HashMap::<&str, &str>::new()).unwrap();
+    assert_eq!(Ok(HashMap::new()), win.get_winhighlight());
+}
+
+#[oxi::test]
+fn win_bool_option_setters() {
+    type Setter = fn(&mut Window, bool) -> api::Result<()>;
+
+    let mut win = Window::current();
+
+    let setters: [(Setter, _); 5] = [
+        (Window::set_cursorline, "cursorline"),
+        (Window::set_number, "number"),
+        (Window::set_relativenumber, "relativenumber"),
+        (Window::set_spell, "spell"),
+        (Window::set_wrap, "wrap"),
+    ];
+
+    for (set, name) in setters {
+        for value in [true, false] {
+            assert_eq!(Ok(()), set(&mut win, value));
+            assert_eq!(Ok(value), win.get_option::<bool>(name), "{name}");
+        }
+    }
+}
+
 #[oxi::test]
 fn win_set_get_del_var() {
     let mut win = Window::current();
@@ -120,3 +312,26 @@ fn win_set_get_del_var() {
     assert_eq!(Ok(42), win.get_var("foo"));
     assert_eq!(Ok(()), win.del_var("foo"));
 }
+
+#[oxi::test]
+fn sidebar_window_toggle() {
+    let buf = api::create_buf(false, true).unwrap();
+    let mut sidebar =
+        api::SidebarWindow::new(buf.clone(), SplitModifier::TopLeft, 30);
+
+    let current = Window::current();
+    let num_wins = api::list_wins().len();
+
+    assert_eq!(Ok(true), sidebar.toggle());
+    assert!(sidebar.is_open());
+    assert_eq!(num_wins + 1, api::list_wins().len());
+    assert_eq!(current, Window::current());
+
+    let win = sidebar.window().unwrap();
+    assert_eq!(Ok(buf), win.get_buf());
+    assert_eq!(Ok(30), win.get_width());
+
+    assert_eq!(Ok(false), sidebar.toggle());
+    assert!(!sidebar.is_open());
+    assert_eq!(num_wins, api::list_wins().len());
+}
@@ -1 +1,6 @@
 mod api;
+#[cfg(feature = "libuv")]
+mod libuv;
+mod logging;
+mod object;
+mod toplevel;
@@ -0,0 +1,76 @@
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::thread;
+
+use nvim_oxi::libuv::AsyncHandle;
+use nvim_oxi::{self as oxi, api, Array};
+
+/// Lets the event loop run the pending async callbacks.
+fn run_loop() {
+    api::exec_lua_object("vim.wait(50)", Array::new()).unwrap();
+}
+
+fn counting_handle() -> (AsyncHandle, Rc<Cell<usize>>) {
+    let count = Rc::new(Cell::new(0));
+
+    let handle = AsyncHandle::new({
+        let count = Rc::clone(&count);
+        move || {
+            count.set(count.get() + 1);
+            Ok::<_, Infallible>(())
+        }
+    })
+    .unwrap();
+
+    (handle, count)
+}
+
+#[oxi::test]
+fn async_handle_dropped_before_callback() {
+    let (handle, count) = counting_handle();
+
+    handle.send().unwrap();
+    drop(handle);
+    run_loop();
+
+    // The wake up coalesces with the one sent on drop, which closes the
+    // handle without calling the callback.
+    assert_eq!(0, count.get());
+    assert_eq!(1, Rc::strong_count(&count));
+}
+
+#[oxi::test]
+fn async_handle_dropped_after_callback() {
+    let (handle, count) = counting_handle();
+
+    handle.send().unwrap();
+    run_loop();
+    assert_eq!(1, count.get());
+
+    drop(handle);
+    run_loop();
+
+    assert_eq!(1, count.get());
+    assert_eq!(1, Rc::strong_count(&count));
+}
+
+#[oxi::test]
+fn async_handle_dropped_on_other_thread() {
+    let (handle, count) = counting_handle();
+
+    let other = handle.clone();
+    drop(handle);
+
+    thread::spawn(move || {
+        other.send().unwrap();
+        drop(other);
+    })
+    .join()
+    .unwrap();
+
+    run_loop();
+
+    assert_eq!(0, count.get());
+    assert_eq!(1, Rc::strong_count(&count));
+}
@@ -0,0 +1,30 @@
+use nvim_oxi::{self as oxi, api, api::types::LogLevel, logging::OxiLogger};
+
+#[oxi::test]
+fn logger_warn() {
+    OxiLogger::init(LogLevel::Warn).unwrap();
+
+    log::info!("not shown");
+    log::warn!("careful");
+
+    assert_eq!(Ok(Some("[WARN] careful".into())), api::exec("messages", true));
+}
+
+#[oxi::test]
+fn logger_other_thread() {
+    OxiLogger::init(LogLevel::Info).unwrap();
+
+    std::thread::spawn(|| log::info!("from another thread")).join().unwrap();
+
+    // Records logged off the main thread are only written once the logger is
+    // flushed from the main thread.
+    let messages = api::exec("messages", true).unwrap().unwrap_or_default();
+    assert!(!messages.contains("from another thread"));
+
+    log::logger().flush();
+
+    assert_eq!(
+        Ok(Some("[INFO] from another thread".into())),
+        api::exec("messages", true)
+    );
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use nvim_oxi::lua::{self, Poppable};
+use nvim_oxi::{
+    self as oxi,
+    Array,
+    Deserializer,
+    Dictionary,
+    FromObject,
+    Object,
+    ObjectKind,
+};
+use serde::Deserialize;
+
+/// Runs `test` on `iterations` fuzzed objects, after making them
+/// representable in Lua.
+fn fuzz(iterations: usize, mut test: impl FnMut(Object)) {
+    oxi::fuzz_objects(iterations, |obj| test(lua_representable(obj)));
+}
+
+/// Turns `obj` into the object it becomes after a round trip through Lua:
+///
+/// - numbers are Lua doubles, popped as integers if they fit in an `i32`;
+/// - setting a table key to `nil` removes it, and a later duplicate key
+///   overwrites an earlier one;
+/// - empty tables are popped as arrays.
+fn lua_representable(obj: Object) -> Object {
+    match obj.kind() {
+        ObjectKind::Integer => number(i64::from_obj(obj).unwrap() as f64),
+
+        ObjectKind::Float => number(f64::from_obj(obj).unwrap()),
+
+        ObjectKind::Array => Array::from_obj(obj)
+            .unwrap()
+            .into_iter()
+            .map(lua_representable)
+            .collect::<Array>()
+            .into(),
+
+        ObjectKind::Dictionary => {
+            let mut entries = Vec::<(oxi::String, Object)>::new();
+            for (key, value) in Dictionary::from_obj(obj).unwrap() {
+                entries.retain(|(k, _)| *k != key);
+                let value = lua_representable(value);
+                if value.is_some() {
+                    entries.push((key, value));
+                }
+            }
+            if entries.is_empty() {
+                Array::new().into()
+            } else {
+                Dictionary::from_iter(entries).into()
+            }
+        },
+
+        _ => obj,
+    }
+}
+
+fn number(n: f64) -> Object {
+    if n == (n as i32) as f64 {
+        Object::from(n as i32)
+    } else {
+        Object::from(n)
+    }
+}
+
+/// Sorts the entries of all the dictionaries in `obj` by key, since Lua
+/// tables don't preserve the insertion order.
+fn sorted(obj: Object) -> Object {
+    match obj.kind() {
+        ObjectKind::Array => Array::from_obj(obj)
+            .unwrap()
+            .into_iter()
+            .map(sorted)
+            .collect::<Array>()
+            .into(),
+
+        ObjectKind::Dictionary => {
+            let mut entries = Dictionary::from_obj(obj)
+                .unwrap()
+                .into_iter()
+                .map(|(key, value)| (key, sorted(value)))
+                .collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Dictionary::from_iter(entries).into()
+        },
+
+        _ => obj,
+    }
+}
+
+/// Pushes `value` on the Lua stack and pops it back as a `T`.
+fn through_lua<V: lua::Pushable, T: Poppable>(value: V) -> T {
+    unsafe {
+        lua::with_state(|lstate| {
+            value.push(lstate).unwrap();
+            T::pop(lstate).unwrap()
+        })
+    }
+}
+
+#[oxi::test]
+fn object_lua_roundtrip() {
+    fuzz(200, |obj| {
+        let popped = through_lua::<_, Object>(obj.clone());
+        assert_eq!(sorted(obj), sorted(popped));
+    });
+}
+
+#[oxi::test]
+fn vec_of_objects_lua_roundtrip() {
+    let mut objs = Vec::new();
+    fuzz(50, |obj| objs.push(obj));
+
+    // A `nil` would leave a hole in the table.
+    let objs = objs.into_iter().filter(Object::is_some).collect::<Vec<_>>();
+
+    let popped = through_lua::<_, Vec<Object>>(objs.clone());
+    assert_eq!(
+        objs.into_iter().map(sorted).collect::<Vec<_>>(),
+        popped.into_iter().map(sorted).collect::<Vec<_>>()
+    );
+}
+
+#[oxi::test]
+fn hashmap_lua_roundtrip() {
+    let mut map = HashMap::new();
+    let mut n = 0;
+    fuzz(50, |obj| {
+        if obj.is_some() {
+            map.insert(oxi::String::from(format!("key{n}")), sorted(obj));
+        }
+        n += 1;
+    });
+
+    let dict = map.clone().into_iter().collect::<Dictionary>();
+    let popped = through_lua::<_, HashMap<oxi::String, Object>>(dict);
+    let popped = popped.into_iter().map(|(k, v)| (k, sorted(v))).collect();
+    assert_eq!(map, popped);
+}
+
+#[oxi::test]
+fn deserialize_lua_object() {
+    fuzz(200, |obj| {
+        let popped = through_lua::<_, Object>(obj);
+        let deserialized =
+            Object::deserialize(Deserializer::new(popped.clone())).unwrap();
+        assert_eq!(popped, deserialized);
+    });
+}
@@ -0,0 +1,290 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::{
+    self as oxi,
+    api,
+    Array,
+    Dictionary,
+    FromObject,
+    Function,
+    Object,
+    ToObject,
+};
+use serde_json::{json, Value};
+
+#[oxi::test]
+fn print_object() {
+    let obj = Object::from(Array::from((
+        1,
+        "foo",
+        Dictionary::from_iter([("bar", true)]),
+    )));
+    oxi::print!("{obj}");
+
+    assert_eq!(
+        Ok(Some("[1, \"foo\", {bar: true}]".into())),
+        api::exec("messages", true)
+    );
+}
+
+#[oxi::test]
+fn print_nvim_string() {
+    let str = oxi::String::from_bytes(b"foo\xffbar".to_vec());
+    oxi::print!("{str}");
+
+    assert_eq!(Ok(Some("foo�bar".into())), api::exec("messages", true));
+}
+
+#[oxi::test]
+fn panic_hook() {
+    oxi::set_panic_hook();
+
+    api::create_user_command(
+        "Panic",
+        |_args| {
+            let _ = std::panic::catch_unwind(|| panic!("boom"));
+            Ok(())
+        },
+        &Default::default(),
+    )
+    .unwrap();
+
+    // `:silent!` keeps the message off stderr but still stores it in
+    // `v:errmsg`.
+    api::command("silent! Panic").unwrap();
+
+    // Restore the default hook for the test harness.
+    let _ = std::panic::take_hook();
+
+    let errmsg = api::get_vvar::<oxi::String>("errmsg").unwrap();
+    assert!(errmsg.to_string_lossy().starts_with("panicked at 'boom'"));
+}
+
+#[oxi::test]
+fn panic_hook_other_thread() {
+    api::set_vvar("errmsg", "").unwrap();
+
+    oxi::set_panic_hook();
+
+    // The panic is reported by the previous hook instead of calling the API
+    // from the spawned thread.
+    let res = std::thread::spawn(|| panic!("boom")).join();
+
+    // Restore the default hook for the test harness.
+    let _ = std::panic::take_hook();
+
+    assert!(res.is_err());
+    assert_eq!(Ok(oxi::String::from("")), api::get_vvar("errmsg"));
+}
+
+#[oxi::test]
+fn question_mark_conversions() {
+    api::command(
+        "lua package.preload['oxi_strlen'] = function() return string.len end",
+    )
+    .unwrap();
+
+    fn strlen(str: &str) -> oxi::Result<usize> {
+        let len = api::lua_require_function("oxi_strlen")?;
+        let len = len.call::<_, Object>(str)?;
+        Ok(usize::from_obj(len)?)
+    }
+
+    assert_eq!(Ok(3), strlen("foo"));
+}
+
+#[oxi::test]
+fn serde_json_value_lua_roundtrip() {
+    let value = json!({
+        "null": null,
+        "bool": true,
+        "int": 42,
+        "float": 4.2,
+        "string": "foo",
+        "array": [1, null, "bar"],
+        "empty_array": [],
+        "empty_object": {},
+    });
+
+    let identity = Function::<Value, Value>::from_fn(Ok::<_, oxi::Error>);
+    assert_eq!(Ok(value.clone()), identity.call(value));
+}
+
+#[oxi::test]
+fn serde_json_value_object_conversions() {
+    let value = json!({ "foo": [1, 2.5, "bar"], "baz": { "qux": true } });
+
+    let args = Array::from_iter([value.clone().to_obj().unwrap()]);
+    let encoded =
+        api::call_function::<_, oxi::String>("json_encode", args).unwrap();
+
+    let decoded =
+        api::call_function::<_, Value>("json_decode", (encoded,)).unwrap();
+
+    assert_eq!(value, decoded);
+}
+
+#[oxi::test]
+fn handles_serde_roundtrip() {
+    let buf = api::create_buf(true, false).unwrap();
+
+    // Buffers are serialized as their bare integer handle.
+    let json = serde_json::to_string(&buf).unwrap();
+    assert!(json.parse::<u32>().is_ok(), "{json}");
+    assert_eq!(buf, serde_json::from_str::<api::Buffer>(&json).unwrap());
+
+    let win = api::get_current_win();
+    let json = serde_json::to_value(&win).unwrap();
+    assert_eq!(win, serde_json::from_value::<api::Window>(json).unwrap());
+
+    let tab = api::get_current_tabpage();
+    let json = serde_json::to_value(&tab).unwrap();
+    assert_eq!(tab, serde_json::from_value::<api::TabPage>(json).unwrap());
+
+    api::set_var("oxi_state", (buf.clone(), win.clone(), tab.clone()))
+        .unwrap();
+    assert_eq!(
+        Ok((buf, win, tab)),
+        api::get_var::<(api::Buffer, api::Window, api::TabPage)>("oxi_state")
+    );
+}
+
+#[oxi::test]
+fn generate_lua_shim() {
+    let shim = oxi::generate_lua_shim!();
+    assert!(shim.contains("local name = \"tests\""));
+
+    // With an explicit name the shim is a `&'static str`.
+    const SHIM: &str = oxi::generate_lua_shim!("foo");
+    assert!(SHIM.contains("local name = \"foo\""));
+    assert_eq!(shim.replace("\"tests\"", "\"foo\""), SHIM);
+
+    // The shim should be valid Lua.
+    let compiles = api::call_function::<_, bool>(
+        "luaeval",
+        ("loadstring(_A) ~= nil", SHIM),
+    );
+    assert_eq!(Ok(true), compiles);
+}
+
+#[oxi::test]
+fn api_errors_are_flattened() {
+    let err = oxi::Error::from(api::command("NotACommand").unwrap_err());
+    assert!(matches!(err, oxi::Error::Nvim(_)), "{err:?}");
+
+    let err =
+        oxi::Error::from(api::Error::IntError(u8::try_from(256).unwrap_err()));
+    assert!(matches!(err, oxi::Error::Api(api::Error::IntError(_))));
+
+    let err = oxi::Error::other("custom");
+    assert!(matches!(err, oxi::Error::Other(ref msg) if msg == "custom"));
+    assert_eq!("custom", err.to_string());
+}
+
+#[oxi::test]
+fn lua_function_call() {
+    use oxi::lua::{self, Poppable};
+
+    let tostring = unsafe {
+        lua::with_state(|lstate| {
+            lua::ffi::lua_getglobal(lstate, lua::cstr!("tostring"));
+            oxi::LuaFunction::pop(lstate)
+        })
+    }
+    .unwrap();
+
+    let cloned = tostring.clone();
+    drop(tostring);
+
+    assert_eq!("true", cloned.call::<_, String>(true).unwrap());
+    assert_eq!("1.5", cloned.call::<_, String>(1.5).unwrap());
+}
+
+#[oxi::test]
+fn lua_function_from_object() {
+    let double = Function::from_fn(|n: i64| Ok::<_, oxi::Error>(n * 2));
+    let obj = Object::from(double);
+    assert_eq!(oxi::ObjectKind::LuaRef, obj.kind());
+
+    let double = oxi::LuaFunction::from_obj(obj).unwrap();
+    assert_eq!(42, double.call::<_, i64>(21i64).unwrap());
+}
+
+#[oxi::test]
+fn schedule_nested() {
+    let order = Rc::new(RefCell::new(Vec::new()));
+
+    oxi::schedule({
+        let order = Rc::clone(&order);
+        move |_| {
+            order.borrow_mut().push("outer");
+            oxi::schedule({
+                let order = Rc::clone(&order);
+                move |_| {
+                    order.borrow_mut().push("nested");
+                    Ok(())
+                }
+            });
+            // The nested callback is queued, not run right away.
+            order.borrow_mut().push("outer end");
+            Ok(())
+        }
+    });
+
+    oxi::schedule({
+        let order = Rc::clone(&order);
+        move |_| {
+            order.borrow_mut().push("second");
+            Ok(())
+        }
+    });
+
+    assert!(order.borrow().is_empty());
+
+    // Let the event loop run the scheduled callbacks.
+    api::exec_lua_object("vim.wait(50)", Array::new()).unwrap();
+
+    assert_eq!(
+        vec!["outer", "outer end", "second", "nested"],
+        *order.borrow()
+    );
+}
+
+#[cfg(feature = "libuv")]
+#[oxi::test]
+fn feed_script() {
+    use std::time::Duration;
+
+    let buf = api::Buffer::current();
+
+    let lines = |buf: &api::Buffer| {
+        buf.get_lines(0, 1, true)
+            .unwrap()
+            .flat_map(TryFrom::try_from)
+            .collect::<Vec<String>>()
+    };
+
+    let seq_cur = || {
+        let tree =
+            api::call_function::<_, Dictionary>("undotree", Array::new())
+                .unwrap();
+        i64::from_obj(tree.get("seq_cur").unwrap().clone()).unwrap()
+    };
+
+    let res = api::feed_script(&[
+        ("ihello<Esc>", Duration::from_millis(10)),
+        ("u", Duration::ZERO),
+    ]);
+    assert!(res.is_ok(), "{res:?}");
+
+    // Only the first step has run so far.
+    assert_eq!(vec!["hello"], lines(&buf));
+    assert_eq!(1, seq_cur());
+
+    // Let the timer fire and the scheduled step run.
+    api::exec_lua_object("vim.wait(50)", Array::new()).unwrap();
+
+    assert_eq!(vec![""], lines(&buf));
+    assert_eq!(0, seq_cur());
+}